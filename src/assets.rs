@@ -0,0 +1,141 @@
+//! アセットの所在の解決
+//! 環境変数・実行ファイルの隣・作業ディレクトリの順に`assets`を探す
+//! `embed-assets`機能を有効にした場合は、実行ファイルに埋め込んだものを用いる
+
+#[cfg(not(feature = "embed-assets"))]
+use std::path::PathBuf;
+
+/// アセットのディレクトリを直接指定する環境変数
+#[cfg(not(feature = "embed-assets"))]
+pub const ASSET_DIR_ENV: &str = "FERRIS_SHOOTING_ASSETS";
+
+/// 同梱するアセットの一覧から、読み込む側の一覧と埋め込むアセットを生成する
+/// アセットを増やす時は`bundle!`の呼び出しだけを書き換える
+macro_rules! bundle {
+    (
+        sprites: [$($sprite:literal),* $(,)?], 
+        anims: [$($anim:literal),* $(,)?], 
+        layers: $layer:literal $(,)?
+    ) => {
+        /// アトラスに詰め込む画像の名前
+        /// `assets/images/<名前>.png`を読み込む
+        pub const SPRITES: &[&str] = &[$($sprite),*];
+
+        /// 読み込むクリップ定義ファイル
+        pub const ANIM_FILES: &[&str] = &[$($anim),*];
+
+        /// 読み込む背景の層定義ファイル
+        pub const LAYER_FILE: &str = $layer;
+
+        /// 実行ファイルに埋め込むアセット
+        #[cfg(feature = "embed-assets")]
+        const EMBEDDED: &[(&str, &[u8])] = &[
+            $((
+                concat!("images/", $sprite, ".png"), 
+                include_bytes!(concat!("../assets/images/", $sprite, ".png")), 
+            ), )*
+            $(($anim, include_bytes!(concat!("../assets/", $anim))), )*
+            ($layer, include_bytes!(concat!("../assets/", $layer))), 
+        ];
+    };
+}
+
+bundle! {
+    sprites: [
+        "ferris", 
+        "aim", 
+        "gear", 
+        "enemy_sprite", 
+        "item", 
+        "particle", 
+        "status", 
+        "bar", 
+        "indicator", 
+        "background", 
+        "letterbox", 
+        "font", 
+    ], 
+    anims: [
+        "images/enemy_sprite.anim", 
+        "images/ferris.anim", 
+        "images/aim.anim", 
+    ], 
+    layers: "images/background.layers", 
+}
+
+/// アセットが見つからなかった時のエラー
+#[derive(Debug)]
+pub struct AssetError {
+    pub name: String, 
+    /// 探した場所
+    #[cfg(not(feature = "embed-assets"))]
+    pub tried: Vec<PathBuf>, 
+}
+impl std::fmt::Display for AssetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "asset `{}` was not found", self.name)?;
+        #[cfg(feature = "embed-assets")]
+        { write!(f, " in the embedded assets") }
+        #[cfg(not(feature = "embed-assets"))]
+        {
+            write!(f, ", tried:")?;
+            for p in self.tried.iter() {
+                write!(f, "\n    {}", p.display())?;
+            }
+            write!(f, "\n(set {} to the assets directory to override)", ASSET_DIR_ENV)
+        }
+    }
+}
+impl std::error::Error for AssetError {}
+
+/// `assets`ディレクトリの候補
+#[cfg(not(feature = "embed-assets"))]
+pub fn roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+    if let Some(dir) = std::env::var_os(ASSET_DIR_ENV) {
+        roots.push(PathBuf::from(dir))
+    }
+    if let Some(dir) = std::env::current_exe().ok()
+        .and_then(|p| p.parent().map(|p| p.to_path_buf()))
+    {
+        roots.push(dir.join("assets"))
+    }
+    if let Ok(dir) = std::env::current_dir() {
+        roots.push(dir.join("assets"))
+    }
+    roots
+}
+
+/// `assets`からの相対パスで指定したアセットのファイルの場所
+#[cfg(not(feature = "embed-assets"))]
+pub fn resolve(name: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let mut tried = Vec::new();
+    for root in roots() {
+        let path = root.join(name);
+        if path.is_file() { return Ok(path) }
+        tried.push(path)
+    }
+    Err(AssetError { name: name.to_string(), tried }.into())
+}
+
+#[cfg(feature = "embed-assets")]
+fn embedded(name: &str) -> Result<&'static [u8], AssetError> {
+    EMBEDDED.iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, b)| *b)
+        .ok_or_else(|| AssetError { name: name.to_string() })
+}
+
+/// アセットの中身を読み込む
+pub fn read(name: &str) -> Result<std::borrow::Cow<'static, [u8]>, Box<dyn std::error::Error>> {
+    #[cfg(feature = "embed-assets")]
+    { Ok(std::borrow::Cow::Borrowed(embedded(name)?)) }
+    #[cfg(not(feature = "embed-assets"))]
+    { Ok(std::borrow::Cow::Owned(std::fs::read(resolve(name)?)?)) }
+}
+
+/// アセットの中身を文字列として読み込む
+pub fn read_to_string(name: &str) -> Result<String, Box<dyn std::error::Error>> {
+    Ok(String::from_utf8(read(name)?.into_owned())?)
+}
+
@@ -0,0 +1,195 @@
+//! テクスチャアトラスの実装
+//! 起動時に画像を1枚に詰め込み、名前から領域を引けるようにする
+
+use std::cell::RefCell;
+
+use hashbrown::{HashMap, HashSet};
+
+/// アトラスの幅
+pub const ATLAS_WIDTH: u32 = 1024;
+
+/// 画像同士の間隔
+/// 拡大縮小時に隣の画像がにじまないよう空けておく
+pub const PADDING: u32 = 2;
+
+thread_local! {
+    static ATLAS: RefCell<Atlas> = RefCell::new(Atlas::empty());
+}
+
+/// 画像を詰め込んだアトラス
+pub struct Atlas {
+    /// 画像ごとの左上の位置
+    coords: HashMap<String, [f32; 2]>, 
+    /// 警告済みの見つからなかった名前
+    missing: RefCell<HashSet<String>>, 
+}
+impl Atlas {
+    pub fn empty() -> Self { Self {
+        coords: HashMap::new(), 
+        missing: RefCell::new(HashSet::new()), 
+    }}
+
+    /// 画像を読み込んで詰め込み、アトラスとその画像を返す
+    pub fn build(
+        names: &[&str], 
+    ) -> Result<(Self, image::RgbaImage), Box<dyn std::error::Error>> {
+        let mut images = names.iter()
+            .map(|name| -> Result<_, Box<dyn std::error::Error>> {
+                let path = format!("images/{}.png", name);
+                let bytes = crate::assets::read(&path)?;
+                let img = image::load_from_memory(&bytes)
+                    .map_err(|e| format!("failed to decode {}: {}", path, e))?;
+                Ok((*name, img.to_rgba8()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        images.sort_by(|a, b| b.1.height().cmp(&a.1.height()));
+
+        let sizes = images.iter()
+            .map(|(_, img)| [img.width(), img.height()])
+            .collect::<Vec<_>>();
+        let (places, height) = pack(&sizes, ATLAS_WIDTH, PADDING)
+            .map_err(|i| format!("{} is wider than the atlas", images[i].0))?;
+
+        let mut coords = HashMap::new();
+        let mut atlas = image::RgbaImage::new(ATLAS_WIDTH, height);
+        for ((name, img), [x, y]) in images.iter().zip(places) {
+            coords.insert(name.to_string(), [x as f32, y as f32]);
+            image::imageops::replace(&mut atlas, img, x as i64, y as i64);
+        }
+
+        Ok((
+            Self {
+                coords, 
+                missing: RefCell::new(HashSet::new()), 
+            }, 
+            atlas, 
+        ))
+    }
+
+    /// 画像内の座標をアトラス上の座標へ変換する
+    /// 詰め込まれていない名前であれば、名前ごとに一度だけ警告してそのままの座標を返す
+    pub fn locate(&self, name: &str, tex_coord: [f32; 2]) -> [f32; 2] {
+        match self.coords.get(name) {
+            Some(c) => [c[0] + tex_coord[0], c[1] + tex_coord[1]], 
+            None => {
+                if self.missing.borrow_mut().insert(name.to_string()) {
+                    log::warn!("sprite `{}` is not packed into the atlas", name)
+                }
+                tex_coord
+            }, 
+        }
+    }
+}
+
+/// 大きさの列を幅widthの中へ棚状に詰め込む
+/// 与えた順に左から並べ、幅を超える場合は次の棚へ移る
+/// 各要素の左上の位置と、2の冪に切り上げた全体の高さを返す
+/// 幅に収まらない要素があれば、その添字を返す
+pub fn pack(
+    sizes: &[[u32; 2]], 
+    width: u32, 
+    padding: u32, 
+) -> Result<(Vec<[u32; 2]>, u32), usize> {
+    let mut places = Vec::with_capacity(sizes.len());
+    let (mut x, mut y, mut shelf) = (0, 0, 0);
+    for (i, &[w, h]) in sizes.iter().enumerate() {
+        if width < w { return Err(i) }
+        if width < x + w {
+            x = 0;
+            y += shelf + padding;
+            shelf = 0;
+        }
+        places.push([x, y]);
+        x += w + padding;
+        shelf = shelf.max(h);
+    }
+    Ok((places, (y + shelf).max(1).next_power_of_two()))
+}
+
+/// 詰め込んだ画像を描画ライブラリへ渡すための一時ファイル
+/// 描画ライブラリは画像をファイルからしか読み込めないため、読み込みの間だけ書き出す
+/// 複数のプロセスが同時に起動しても衝突しないようプロセスごとに名前を分け、破棄時に削除する
+pub struct StagedImage {
+    path: std::path::PathBuf, 
+}
+impl StagedImage {
+    /// 一時ディレクトリ、書き込めなければ作業ディレクトリへ書き出す
+    pub fn write(img: &image::RgbaImage) -> Result<Self, Box<dyn std::error::Error>> {
+        let name = format!("ferris-shooting-atlas-{}.png", std::process::id());
+        let mut errors = Vec::new();
+        for dir in [Some(std::env::temp_dir()), std::env::current_dir().ok()].into_iter().flatten() {
+            let path = dir.join(&name);
+            match img.save(&path) {
+                Ok(()) => return Ok(Self { path }), 
+                Err(e) => errors.push(format!("{}: {}", path.display(), e)), 
+            }
+        }
+        Err(format!("failed to stage the atlas image:\n    {}", errors.join("\n    ")).into())
+    }
+
+    pub fn path_str(&self) -> Result<&str, Box<dyn std::error::Error>> {
+        Ok(self.path.to_str().ok_or("atlas staging path is not valid UTF-8")?)
+    }
+}
+impl Drop for StagedImage {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// 描画に用いるアトラスを設定する
+pub fn install(atlas: Atlas) {
+    ATLAS.with(|a| *a.borrow_mut() = atlas)
+}
+
+/// 設定済みのアトラス上の座標へ変換する
+pub fn locate(name: &str, tex_coord: [f32; 2]) -> [f32; 2] {
+    ATLAS.with(|a| a.borrow().locate(name, tex_coord))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_single_shelf() {
+        let (places, height) = pack(&[[64, 32], [32, 16], [16, 16]], 256, 2).unwrap();
+        assert_eq!(places, vec![[0, 0], [66, 0], [100, 0]]);
+        assert_eq!(height, 32);
+    }
+
+    #[test]
+    fn pack_wraps_to_next_shelf() {
+        // 3つ目は幅に収まらないため、1段目の高さと間隔の分だけ下の棚へ移る
+        let (places, height) = pack(&[[100, 40], [100, 30], [100, 20]], 220, 2).unwrap();
+        assert_eq!(places, vec![[0, 0], [102, 0], [0, 42]]);
+        assert_eq!(height, 64);
+    }
+
+    #[test]
+    fn pack_exact_width_fits() {
+        let (places, _) = pack(&[[128, 8], [126, 8]], 256, 2).unwrap();
+        assert_eq!(places, vec![[0, 0], [130, 0]]);
+    }
+
+    #[test]
+    fn pack_rejects_overflow() {
+        assert_eq!(pack(&[[16, 16], [300, 16]], 256, 2), Err(1));
+    }
+
+    #[test]
+    fn pack_empty() {
+        assert_eq!(pack(&[], 256, 2), Ok((vec![], 1)));
+    }
+
+    #[test]
+    fn locate_unknown_name_is_warned_once() {
+        let mut atlas = Atlas::empty();
+        atlas.coords.insert("ferris".to_string(), [64., 32.]);
+        assert_eq!(atlas.locate("ferris", [1., 2.]), [65., 34.]);
+        assert_eq!(atlas.locate("feris", [1., 2.]), [1., 2.]);
+        assert_eq!(atlas.locate("feris", [3., 4.]), [3., 4.]);
+        assert_eq!(atlas.missing.borrow().len(), 1);
+        assert!(atlas.missing.borrow().contains("feris"));
+    }
+}
@@ -0,0 +1,234 @@
+//! スプライトシートによるアニメーションの実装
+//! クリップの定義は画像と同じ場所にある`.anim`ファイルから読み込む
+
+use std::rc::Rc;
+
+use hashbrown::HashMap;
+
+thread_local! {
+    static LIBRARY: crate::ULazy<ClipLibrary> = crate::ULazy::new(|| {
+        let mut lib = ClipLibrary::new();
+        for path in crate::assets::ANIM_FILES {
+            // 読み込めなかった場合は静止画のまま描画する
+            if let Err(e) = lib.load(path) {
+                log::warn!("failed to load animation clips {}: {}", path, e)
+            }
+        }
+        lib
+    });
+}
+
+/// 再生の方式
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum AnimMode {
+    /// 最後のフレームの後は最初に戻る
+    Loop, 
+    /// 最後のフレームで止まる
+    Once, 
+}
+
+/// アニメーションの1フレーム
+pub struct Frame {
+    pub tex_coord: [f32; 2], 
+    pub tex_size: [f32; 2], 
+    /// 表示時間
+    pub dur: f32, 
+    /// このフレームに入った時に発生するイベント
+    pub event: Option<String>, 
+}
+
+/// 名前付きのフレームの並び
+pub struct Clip {
+    pub name: String, 
+    pub mode: AnimMode, 
+    pub frames: Vec<Frame>, 
+}
+
+/// クリップの一覧
+pub struct ClipLibrary(HashMap<String, Rc<Clip>>);
+impl ClipLibrary {
+    pub fn new() -> Self { Self(HashMap::new()) }
+
+    /// アセットからクリップ定義を読み込む
+    pub fn load(&mut self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let src = crate::assets::read_to_string(path)?;
+        self.parse(&src)
+    }
+
+    /// クリップ定義の解釈
+    /// `#`以降はコメントとして読み飛ばす
+    /// ```text
+    /// clip <名前> <loop|once>
+    /// frame <x> <y> <幅> <高さ> <表示時間(秒)> [イベント名]
+    /// ```
+    pub fn parse(&mut self, src: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut current: Option<Clip> = None;
+        for mut line in super::def_file::lines(src) {
+            match line.directive {
+                "clip" => {
+                    if let Some(clip) = current.take() { self.insert(clip)? }
+                    let name = line.word("clip name")?;
+                    let mode = match line.next_word() {
+                        Some("loop") => AnimMode::Loop, 
+                        Some("once") => AnimMode::Once, 
+                        m => return Err(line.error(format_args!("unknown mode {:?}", m))), 
+                    };
+                    current = Some(Clip {
+                        name: name.to_string(), 
+                        mode, 
+                        frames: Vec::new(), 
+                    })
+                }, 
+                "frame" => {
+                    let clip = current.as_mut()
+                        .ok_or_else(|| line.error("frame outside of clip"))?;
+                    let (x, y, w, h) = (
+                        line.num("frame value")?, 
+                        line.num("frame value")?, 
+                        line.num("frame value")?, 
+                        line.num("frame value")?, 
+                    );
+                    let dur = line.num("frame value")?;
+                    if dur <= 0. {
+                        return Err(line.error("frame duration must be positive"))
+                    }
+                    clip.frames.push(Frame {
+                        tex_coord: [x, y], 
+                        tex_size: [w, h], 
+                        dur, 
+                        event: line.next_word().map(|s| s.to_string()), 
+                    })
+                }, 
+                _ => return Err(line.unknown()), 
+            }
+        }
+        if let Some(clip) = current.take() { self.insert(clip)? }
+        Ok(())
+    }
+
+    fn insert(&mut self, clip: Clip) -> Result<(), Box<dyn std::error::Error>> {
+        if clip.frames.is_empty() {
+            return Err(format!("clip {} has no frames", clip.name).into())
+        }
+        self.0.insert(clip.name.clone(), Rc::new(clip));
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<Rc<Clip>> {
+        self.0.get(name).cloned()
+    }
+}
+
+/// クリップの再生状態
+pub struct Animator {
+    clip: Option<Rc<Clip>>, 
+    frame: usize, 
+    time: f32, 
+    finished: bool, 
+}
+impl Animator {
+    pub fn new(name: &str) -> Self { Self {
+        clip: LIBRARY.with(|l| l.get(name)), 
+        frame: 0, 
+        time: 0., 
+        finished: false, 
+    }}
+
+    /// クリップを切り替える
+    /// 再生中のクリップと同じなら何もしない
+    pub fn play(&mut self, name: &str) {
+        if self.clip.as_ref().map_or(false, |c| c.name == name) { return }
+        *self = Self::new(name)
+    }
+
+    /// 最初から再生し直す
+    pub fn restart(&mut self) {
+        self.frame = 0;
+        self.time = 0.;
+        self.finished = false;
+    }
+
+    /// 時間経過の処理
+    /// このティックの間に入ったフレームのイベントを順にon_eventへ渡す
+    pub fn update(&mut self, dt: f32, mut on_event: impl FnMut(&str)) {
+        let clip = match &self.clip {
+            Some(clip) => clip, 
+            None => return, 
+        };
+        if self.finished { return }
+        self.time += dt;
+        while clip.frames[self.frame].dur <= self.time {
+            self.time -= clip.frames[self.frame].dur;
+            if self.frame + 1 < clip.frames.len() {
+                self.frame += 1
+            } else if clip.mode == AnimMode::Loop {
+                self.frame = 0
+            } else {
+                self.finished = true;
+                self.time = 0.;
+                break
+            }
+            if let Some(ev) = &clip.frames[self.frame].event {
+                on_event(ev)
+            }
+        }
+    }
+
+    /// 一回再生のクリップが最後まで再生されたか
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// 現在のフレームのテクスチャ座標と大きさ
+    /// クリップが無ければNone
+    pub fn tex_rect(&self) -> Option<([f32; 2], [f32; 2])> {
+        self.clip.as_ref()
+            .map(|c| &c.frames[self.frame])
+            .map(|f| (f.tex_coord, f.tex_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid() {
+        let mut lib = ClipLibrary::new();
+        lib.parse("\
+            # コメント\n\
+            clip walk loop\n\
+            frame 0 0 32 32 0.1\n\
+            frame 32 0 32 32 0.2 glitch # 行末のコメント\n\
+            \n\
+            clip die once\n\
+            frame 0 32 16 16 0.5\n\
+        ").unwrap();
+        let walk = lib.get("walk").unwrap();
+        assert!(walk.mode == AnimMode::Loop);
+        assert_eq!(walk.frames.len(), 2);
+        assert_eq!(walk.frames[1].tex_coord, [32., 0.]);
+        assert_eq!(walk.frames[1].event.as_deref(), Some("glitch"));
+        assert!(walk.frames[0].event.is_none());
+        let die = lib.get("die").unwrap();
+        assert!(die.mode == AnimMode::Once);
+        assert_eq!(die.frames[0].tex_size, [16., 16.]);
+        assert!(lib.get("idle").is_none());
+    }
+
+    #[test]
+    fn parse_missing_name() {
+        assert!(ClipLibrary::new().parse("clip\nframe 0 0 32 32 0.1\n").is_err());
+    }
+
+    #[test]
+    fn parse_frame_outside_clip() {
+        assert!(ClipLibrary::new().parse("frame 0 0 32 32 0.1\n").is_err());
+    }
+
+    #[test]
+    fn parse_non_positive_duration() {
+        assert!(ClipLibrary::new().parse("clip a loop\nframe 0 0 32 32 0\n").is_err());
+        assert!(ClipLibrary::new().parse("clip a loop\nframe 0 0 32 32 -0.1\n").is_err());
+    }
+}
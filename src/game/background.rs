@@ -0,0 +1,200 @@
+//! 視差スクロールする背景の実装
+//! 層の定義は画像と同じ場所にある`.layers`ファイルから読み込む
+
+use super::*;
+
+/// 背景の1層
+/// 同じ画像を敷き詰め、層ごとの速度で流す
+pub struct ParallaxLayer {
+    pub name: String, 
+    pub tex_coord: [f32; 2], 
+    pub tex_size: [f32; 2], 
+    /// 画面上での拡大率
+    pub scale: f32, 
+    /// スクロールの速度
+    pub velocity: [f32; 2], 
+    /// 敷き詰めの基準位置のずれ
+    offset: [f32; 2], 
+}
+impl ParallaxLayer {
+    /// 画面上での1枚の大きさ
+    pub fn tile_size(&self) -> [f32; 2] {
+        [self.tex_size[0] * self.scale, self.tex_size[1] * self.scale]
+    }
+
+    pub fn update(&mut self, cycle: &cycle_measure::CycleMeasure) {
+        let tile = self.tile_size();
+        for i in 0..2 {
+            self.offset[i] = (self.offset[i] + self.velocity[i] * cycle.dur)
+                .rem_euclid(tile[i]);
+        }
+    }
+}
+
+/// 画面を覆うように敷き詰めた層
+struct LayerTiles<'a> {
+    layer: &'a ParallaxLayer, 
+    screen: hud::Screen, 
+}
+impl InstanceGen<ImgObjInstance> for LayerTiles<'_> {
+    fn generate(
+        &self, 
+        instances: &mut simple2d::instance::buffer::InstanceArray<ImgObjInstance>
+    ) {
+        let tile = self.layer.tile_size();
+        let tex_coord = crate::atlas::locate("background", self.layer.tex_coord);
+        // 画面の左下の端を含む1枚の中心から敷き詰め始める
+        let start = [0, 1].map(|i| {
+            let min = self.screen.center[i] - self.screen.half[i];
+            let n = ((min - self.layer.offset[i]) / tile[i]).floor();
+            self.layer.offset[i] + n * tile[i] + tile[i] * 0.5
+        });
+        let count = [0, 1].map(|i|
+            (self.screen.half[i] * 2. / tile[i]).ceil() as u32 + 1
+        );
+        for ix in 0..count[0] { for iy in 0..count[1] {
+            instances.push(ImgObjInstance {
+                position: [
+                    start[0] + ix as f32 * tile[0], 
+                    start[1] + iy as f32 * tile[1], 
+                ], 
+                size: tile, 
+                rotation: 0., 
+                tex_coord, 
+                tex_size: self.layer.tex_size, 
+                tex_rev: [false, false], 
+            });
+        }}
+    }
+}
+
+/// 視差スクロールする背景
+pub struct Background {
+    layers: Vec<ParallaxLayer>, 
+}
+impl Background {
+    pub fn new() -> Self {
+        let layers = match Self::load(crate::assets::LAYER_FILE) {
+            Ok(layers) => layers, 
+            // 読み込めなかった場合は背景を描画しない
+            Err(e) => {
+                log::warn!("failed to load background layers {}: {}", crate::assets::LAYER_FILE, e);
+                Vec::new()
+            }, 
+        };
+        Self { layers }
+    }
+
+    /// アセットから層定義を読み込む
+    pub fn load(path: &str) -> Result<Vec<ParallaxLayer>, Box<dyn std::error::Error>> {
+        let src = crate::assets::read_to_string(path)?;
+        Self::parse(&src)
+    }
+
+    /// 層定義の解釈
+    /// `#`以降はコメントとして読み飛ばす
+    /// ```text
+    /// layer <名前> <x> <y> <幅> <高さ> <拡大率> <速度x(px/秒)> <速度y(px/秒)>
+    /// ```
+    pub fn parse(src: &str) -> Result<Vec<ParallaxLayer>, Box<dyn std::error::Error>> {
+        let mut layers = Vec::new();
+        for mut line in super::def_file::lines(src) {
+            match line.directive {
+                "layer" => {
+                    let name = line.word("layer name")?;
+                    let (x, y, w, h) = (
+                        line.num("layer value")?, 
+                        line.num("layer value")?, 
+                        line.num("layer value")?, 
+                        line.num("layer value")?, 
+                    );
+                    let (scale, vx, vy) = (
+                        line.num("layer value")?, 
+                        line.num("layer value")?, 
+                        line.num("layer value")?, 
+                    );
+                    if w <= 0. || h <= 0. || scale <= 0. {
+                        return Err(line.error("layer size must be positive"))
+                    }
+                    layers.push(ParallaxLayer {
+                        name: name.to_string(), 
+                        tex_coord: [x, y], 
+                        tex_size: [w, h], 
+                        scale, 
+                        velocity: [vx, vy], 
+                        offset: [0., 0.], 
+                    })
+                }, 
+                _ => return Err(line.unknown()), 
+            }
+        }
+        Ok(layers)
+    }
+
+    pub fn update(&mut self, cycle: &cycle_measure::CycleMeasure) {
+        self.layers.iter_mut().for_each(|l| l.update(cycle));
+    }
+
+    /// 背景の層へ奥の層から順に積む
+    pub fn rendering(&self, renderer: &mut crate::renderer::FSRenderer) {
+        let screen = hud::Screen::from_camera(&renderer.camera.camera);
+        for layer in self.layers.iter() {
+            renderer.sprite(crate::render_queue::Layer::Background)
+                .push_instance(&LayerTiles { layer, screen });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_layers() {
+        let layers = Background::parse("\
+            # 奥の層から順に並べる\n\
+            layer far 0 0 256 256 2 0 -20\n\
+            \n\
+            layer near 0 256 128 128 1.5 4 -60 # 手前の層\n\
+        ").unwrap();
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[0].name, "far");
+        assert_eq!(layers[0].tile_size(), [512., 512.]);
+        assert_eq!(layers[1].tex_coord, [0., 256.]);
+        assert_eq!(layers[1].velocity, [4., -60.]);
+        assert!(Background::parse("layer bad 0 0 0 128 1 0 0").is_err());
+        assert!(Background::parse("layer short 0 0 128").is_err());
+        assert!(Background::parse("tile far 0 0 256 256 2 0 -20").is_err());
+    }
+
+    #[test]
+    fn tiles_cover_screen_with_negative_offsets() {
+        for offset in [[0., 0.], [-30., -250.], [-511., 17.]] {
+            for center in [[0., 0.], [-1000., -333.], [250., -4096.]] {
+                let layer = ParallaxLayer {
+                    name: "test".to_string(), 
+                    tex_coord: [0., 0.], 
+                    tex_size: [100., 60.], 
+                    scale: 1.5, 
+                    velocity: [0., 0.], 
+                    offset, 
+                };
+                let screen = hud::Screen { center, half: [320., 480.] };
+                let mut instances = simple2d::instance::buffer::InstanceArray::new();
+                LayerTiles { layer: &layer, screen }.generate(&mut instances);
+
+                let tile = layer.tile_size();
+                for i in 0..2 {
+                    let lo = instances.iter()
+                        .map(|t| t.position[i] - tile[i] * 0.5)
+                        .fold(f32::MAX, f32::min);
+                    let hi = instances.iter()
+                        .map(|t| t.position[i] + tile[i] * 0.5)
+                        .fold(f32::MIN, f32::max);
+                    assert!(lo <= screen.center[i] - screen.half[i]);
+                    assert!(screen.center[i] + screen.half[i] <= hi);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! カメラ演出の実装
+//! 画面の揺れ・ズームの脈動・時間の減速を扱う
+//! 描画用のカメラにのみ作用し、ゲームプレイ用の表示領域には影響しない
+
+use rand::Rng;
+
+use super::*;
+
+/// 揺れの強さが1の時の最大の変位
+pub const SHAKE_OFFSET_MAX: f32 = 24.;
+
+/// 揺れの強さが1の時の最大の回転
+pub const SHAKE_ROTATION_MAX: f32 = 3. * (std::f32::consts::PI / 180.);
+
+/// 揺れの強さの毎秒の減衰量
+pub const TRAUMA_DECAY: f32 = 1.5;
+
+/// ズームの脈動の毎秒の減衰率
+pub const ZOOM_DECAY: f32 = 8.;
+
+/// 大きい爆発とみなす半径
+pub const LARGE_EXPLOSION_RADIUS: f32 = 128.;
+
+/// カメラ演出を引き起こす出来事
+#[derive(Clone, Copy)]
+pub enum CameraEvent {
+    /// 大きい爆発
+    LargeExplosion {
+        radius: f32, 
+    }, 
+    /// 敵の取り逃し
+    Leak, 
+    /// DangPtrの撃破
+    DangPtrKill, 
+}
+impl CameraEvent {
+    /// 加える揺れの強さ
+    pub fn trauma(&self) -> f32 { match self {
+        CameraEvent::LargeExplosion { radius } => (radius / 480.).min(0.6), 
+        CameraEvent::Leak => 0.5, 
+        CameraEvent::DangPtrKill => 0.3, 
+    }}
+
+    /// 加えるズームの脈動
+    pub fn zoom(&self) -> f32 { match self {
+        CameraEvent::LargeExplosion { .. } => 0.03, 
+        CameraEvent::Leak => -0.04, 
+        CameraEvent::DangPtrKill => 0.06, 
+    }}
+
+    /// 時間の減速(倍率, 実時間での継続時間)
+    pub fn slowdown(&self) -> Option<(f32, f32)> { match self {
+        CameraEvent::DangPtrKill => Some((0.3, 0.25)), 
+        _ => None, 
+    }}
+}
+
+/// 揺れ・ズームの演出の強さの設定
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ShakeLevel {
+    Full, 
+    Reduced, 
+    Off, 
+}
+impl ShakeLevel {
+    pub fn scale(&self) -> f32 { match self {
+        ShakeLevel::Full => 1., 
+        ShakeLevel::Reduced => 0.35, 
+        ShakeLevel::Off => 0., 
+    }}
+
+    pub fn next(&self) -> Self { match self {
+        ShakeLevel::Full => ShakeLevel::Reduced, 
+        ShakeLevel::Reduced => ShakeLevel::Off, 
+        ShakeLevel::Off => ShakeLevel::Full, 
+    }}
+}
+
+/// カメラ演出の管理
+pub struct CameraFx {
+    trauma: f32, 
+    zoom: f32, 
+    slowdown: Option<(f32, f32)>, 
+    offset: nalgebra::Vector2<f32>, 
+    rotation: f32, 
+    /// 揺れ・ズームの演出の強さ
+    pub shake_level: ShakeLevel, 
+    toggle_shake: Trigger, 
+}
+impl CameraFx {
+    pub fn new() -> Self { Self {
+        trauma: 0., 
+        zoom: 0., 
+        slowdown: None, 
+        offset: [0., 0.].into(), 
+        rotation: 0., 
+        shake_level: ShakeLevel::Full, 
+        toggle_shake: Trigger::default(), 
+    }}
+
+    /// 出来事に応じた演出を加える
+    pub fn push(&mut self, event: CameraEvent) {
+        self.trauma = (self.trauma + event.trauma()).min(1.);
+        self.zoom += event.zoom();
+        if let Some((scale, dur)) = event.slowdown() {
+            // 重なった場合は強い方の倍率と長い方の時間を残す
+            self.slowdown = Some(match self.slowdown {
+                Some((s, t)) => (s.min(scale), t.max(dur)), 
+                None => (scale, dur), 
+            })
+        }
+    }
+
+    /// 爆発の大きさに応じた演出を加える
+    pub fn push_explosion(&mut self, radius: f32) {
+        if LARGE_EXPLOSION_RADIUS <= radius {
+            self.push(CameraEvent::LargeExplosion { radius })
+        }
+    }
+
+    /// 時間経過の処理
+    /// 減速の影響を受けないよう、実時間で渡すこと
+    pub fn update(&mut self, cycle: &cycle_measure::CycleMeasure) {
+        let dt = cycle.dur;
+        self.toggle_shake.update();
+        if self.toggle_shake.get_trig_count() == 1 {
+            self.shake_level = self.shake_level.next();
+        }
+
+        self.trauma = (self.trauma - TRAUMA_DECAY * dt).max(0.);
+        self.zoom *= (1. - ZOOM_DECAY * dt).max(0.);
+        self.slowdown = self.slowdown
+            .map(|(s, t)| (s, t - dt))
+            .filter(|(_, t)| 0. < *t);
+
+        // 強さの二乗で揺らすことで、弱い揺れは目立たなくする
+        let shake = self.trauma.powi(2) * self.shake_level.scale();
+        let (offset, rotation) = crate::RNG.with(|r| {
+            let mut rng = r.borrow_mut();
+            (
+                nalgebra::Vector2::new(
+                    rng.gen_range(-1.0..=1.0), 
+                    rng.gen_range(-1.0..=1.0), 
+                ) * SHAKE_OFFSET_MAX * shake, 
+                rng.gen_range(-1.0..=1.0) * SHAKE_ROTATION_MAX * shake, 
+            )
+        });
+        self.offset = offset;
+        self.rotation = rotation;
+    }
+
+    /// ゲームプレイの時間の倍率
+    pub fn time_scale(&self) -> f32 {
+        self.slowdown.map_or(1., |(s, _)| s)
+    }
+
+    /// 減速を反映した経過時間
+    pub fn scaled_cycle(
+        &self, 
+        cycle: &cycle_measure::CycleMeasure, 
+    ) -> cycle_measure::CycleMeasure {
+        let mut scaled = cycle_measure::CycleMeasure::new();
+        scaled.dur = cycle.dur * self.time_scale();
+        scaled
+    }
+
+    /// 描画用のカメラへ演出を反映する
+    /// ズームはウィンドウへ収める倍率に掛け合わせる
+    pub fn apply(&self, camera: &mut simple2d::types::Camera) {
+        camera.position = [self.offset.x, self.offset.y].into();
+        camera.zoom = crate::viewport::current().scale()
+            * (1. + self.zoom * self.shake_level.scale());
+        camera.rotation = self.rotation;
+    }
+
+    pub fn input_key(&mut self, keycode: VirtualKeyCode, state: ElementState) {
+        match keycode {
+            VirtualKeyCode::M => self.toggle_shake.trigger(state), 
+            _ => {}, 
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! アセットの定義ファイルの共通の読み方
+//! 1行に1つの指示を書き、`#`以降はコメントとして読み飛ばす
+//! ```text
+//! <指示> <値> <値> ...
+//! ```
+
+/// 定義ファイルの空でない1行
+pub struct DefLine<'a> {
+    /// 1から始まる行番号
+    pub lnum: usize, 
+    /// 行頭の語
+    pub directive: &'a str, 
+    words: std::str::SplitWhitespace<'a>, 
+}
+impl<'a> DefLine<'a> {
+    /// 行番号を付けたエラー
+    pub fn error(&self, msg: impl std::fmt::Display) -> Box<dyn std::error::Error> {
+        format!("line {}: {}", self.lnum, msg).into()
+    }
+
+    /// 知らない指示だった時のエラー
+    pub fn unknown(&self) -> Box<dyn std::error::Error> {
+        self.error(format_args!("unknown directive {}", self.directive))
+    }
+
+    /// 省略できる次の語
+    pub fn next_word(&mut self) -> Option<&'a str> {
+        self.words.next()
+    }
+
+    /// 次の語
+    /// 無ければwhatが足りないというエラー
+    pub fn word(&mut self, what: &str) -> Result<&'a str, Box<dyn std::error::Error>> {
+        self.words.next()
+            .ok_or_else(|| self.error(format_args!("missing {}", what)))
+    }
+
+    /// 次の語を数値として読む
+    pub fn num(&mut self, what: &str) -> Result<f32, Box<dyn std::error::Error>> {
+        let w = self.word(what)?;
+        w.parse::<f32>()
+            .map_err(|e| self.error(format_args!("invalid {} {}: {}", what, w, e)))
+    }
+}
+
+/// コメントを除き、空でない行を順に返す
+pub fn lines(src: &str) -> impl Iterator<Item = DefLine<'_>> {
+    src.lines().enumerate().filter_map(|(i, line)| {
+        let mut words = line.split('#').next().unwrap_or("").split_whitespace();
+        words.next().map(|directive| DefLine {
+            lnum: i + 1, 
+            directive, 
+            words, 
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_blank_and_comment_lines() {
+        let lines = lines("# head\n\n  a 1 2 # tail\n   \nb\n").collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!((lines[0].lnum, lines[0].directive), (3, "a"));
+        assert_eq!((lines[1].lnum, lines[1].directive), (5, "b"));
+    }
+
+    #[test]
+    fn reads_words_and_numbers() {
+        let mut line = lines("a name 1.5 x").next().unwrap();
+        assert_eq!(line.word("name").unwrap(), "name");
+        assert_eq!(line.num("value").unwrap(), 1.5);
+        assert!(line.num("value").unwrap_err().to_string().starts_with("line 1: invalid value x"));
+        assert!(line.next_word().is_none());
+        assert_eq!(line.word("name").unwrap_err().to_string(), "line 1: missing name");
+    }
+}
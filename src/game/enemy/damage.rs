@@ -0,0 +1,12 @@
+//! ダメージの種類の実装
+
+/// ダメージの種類
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DamageType {
+    /// 弾丸などの運動エネルギーによるもの
+    Kinetic, 
+    /// 爆発によるもの
+    Explosive, 
+    /// 破片によるもの
+    Fragment, 
+}
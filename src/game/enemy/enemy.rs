@@ -1,19 +1,27 @@
 use rand::Rng;
 
 use super::*;
+use crate::game::item::ItemType;
+use tm_wg_wrapper::util::simple2d::physic::PhysicBody;
+use crate::game::shape::{Collider, HasShape, Shape};
+use super::damage::DamageType;
+use super::status::{StatusEffect, StatusOverlay, StatusSet};
 
 pub struct EnemyArray {
     ident: EnemyIdentMaster, 
     pub enemies: EntityArray<ImgObjInstance, Enemy>, 
+    grid: super::grid::SpatialGrid, 
 }
 impl EnemyArray {
     pub fn new() -> Self { Self {
         ident: EnemyIdentMaster(0), 
         enemies: EntityArray::new([]), 
+        grid: super::grid::SpatialGrid::new(super::grid::CELL_SIZE), 
     }}
 
     pub fn rendering(&self, renderer: &mut crate::renderer::FSRenderer) {
-        renderer.enemy.push_instance(&self.enemies);
+        renderer.sprite(crate::render_queue::Layer::Enemies).push_instance(&self.enemies);
+        renderer.sprite(crate::render_queue::Layer::Enemies).push_instance(&StatusOverlay(&self.enemies));
     }
 
     pub fn spawn(
@@ -43,10 +51,16 @@ impl EnemyArray {
         spawner: &mut super::spawn::EnemySpawnerArray, 
         score: &mut u64, 
         damage: &mut u64, 
+        shield: &mut u64, 
+        items: &mut crate::game::item::ItemArray, 
+        feedback: &mut crate::game::feedback::Feedback, 
+        particles: &mut crate::game::particle::ParticleSystem, 
+        camera: &mut crate::game::camera_fx::CameraFx, 
     ) {
         self.enemies.retain(|
             _idx, entity, 
-        | entity.update(cycle, varea, spawner, score, damage));
+        | entity.update(cycle, varea, spawner, score, damage, shield, items, feedback, particles, camera));
+        self.grid.rebuild(&self.enemies);
     }
 
     pub fn get(
@@ -56,6 +70,85 @@ impl EnemyArray {
         self.enemies.get(enemy_ref.idx)
             .filter(|e| e.ident == enemy_ref.ident)
     }
+
+    pub fn get_mut(
+        &mut self, 
+        enemy_ref: &EnemyRef, 
+    ) -> Option<&mut Enemy> {
+        self.enemies.get_mut(enemy_ref.idx)
+            .filter(|e| e.ident == enemy_ref.ident)
+    }
+
+    /// 矩形と重なる敵の候補
+    pub fn query_aabb(
+        &self, 
+        position: nalgebra::Point2<f32>, 
+        size: nalgebra::Vector2<f32>, 
+    ) -> Vec<EnemyRef> {
+        self.grid.query_aabb(position, size)
+    }
+
+    /// 円と重なる敵の候補
+    pub fn query_radius(
+        &self, 
+        center: nalgebra::Point2<f32>, 
+        radius: f32, 
+    ) -> Vec<EnemyRef> {
+        self.grid.query_radius(center, radius)
+    }
+
+    /// 物体がfromから現在位置まで移動する間に、最初に接触する敵
+    /// 接触した敵と、経路上の接触位置を返す
+    /// 矩形同士の掃引で候補を求め、移動経路をなぞるカプセルと敵の形状で絞り込む
+    /// ignoreに含まれる敵と、同じティックのうちに体力が尽きた敵は無視する
+    pub fn first_swept_contact(
+        &self, 
+        body: &(impl physic::PhysicBody + HasShape), 
+        from: nalgebra::Point2<f32>, 
+        ignore: &[EnemyIdent], 
+    ) -> Option<(EnemyRef, nalgebra::Point2<f32>)> {
+        let to = body.position();
+        let size = body.size();
+        let path = to - from;
+        let path_len = (path.x.powi(2) + path.y.powi(2)).sqrt();
+        let sweep = Collider {
+            position: nalgebra::center(&from, &to), 
+            shape: Shape::Capsule {
+                half_len: path_len * 0.5, 
+                radius: body.collider().shape.radius(), 
+                rotation: f32::atan2(path.y, path.x), 
+            }, 
+        };
+        let center = nalgebra::center(&from, &to);
+        let span = nalgebra::Vector2::new(
+            (to.x - from.x).abs() + size.x, 
+            (to.y - from.y).abs() + size.y, 
+        );
+        self.query_aabb(center, span)
+            .into_iter()
+            .filter_map(|r| self.get(&r).map(|e| (r, e)))
+            .filter(|(_, e)| 0. < e.health() && !ignore.contains(&e.ident))
+            .filter(|(_, e)| sweep.intersects(&e.collider()))
+            .filter_map(|(r, e)| crate::game::sweep::swept_aabb(
+                from, 
+                to, 
+                size, 
+                e.position(), 
+                e.collider().shape.bounds(), 
+            ).map(|t| (t, r)))
+            .fold(
+                None::<(f32, EnemyRef)>, 
+                |
+                    init, 
+                    tg, 
+                | match init {
+                    None => Some(tg), 
+                    Some(e) if tg.0 < e.0 => Some(tg), 
+                    a @ Some(_) => a, 
+                }
+            )
+            .map(|(t, r)| (r, from + (to - from) * t))
+    }
 }
 
 pub struct EnemyIdentMaster(u64);
@@ -68,7 +161,7 @@ impl EnemyIdentMaster {
 }
 
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct EnemyIdent(pub(super) u64);
 
 #[derive(Clone)]
@@ -93,6 +186,14 @@ impl EnemyType {
         Self::DangPtr => [192., 0.], 
     }}
 
+    /// 再生するアニメーションのクリップ名
+    pub fn anim_clip(&self) -> &'static str { match self {
+        Self::UndefBeh => "undef_beh", 
+        Self::NullPtr => "null_ptr", 
+        Self::DataRace => "data_race", 
+        Self::DangPtr => "dang_ptr", 
+    }}
+
     pub fn size(&self) -> nalgebra::Vector2<f32> { match self {
         Self::UndefBeh => [64., 64.].into(), 
         Self::NullPtr => [64., 64.].into(), 
@@ -100,6 +201,15 @@ impl EnemyType {
         Self::DangPtr => [64., 64.].into(), 
     }}
 
+    /// 当たり判定の形状
+    /// rotationは描画上の回転角
+    pub fn shape(&self, rotation: f32) -> Shape { match self {
+        Self::UndefBeh => Shape::Circle { radius: 28. }, 
+        Self::NullPtr => Shape::Obb { size: [48., 48.].into(), rotation }, 
+        Self::DataRace => Shape::Obb { size: [56., 40.].into(), rotation }, 
+        Self::DangPtr => Shape::Capsule { half_len: 14., radius: 18., rotation }, 
+    }}
+
     pub fn vel_0(&self) -> f32 { match self {
         Self::UndefBeh => 180., 
         Self::NullPtr => 360., 
@@ -141,6 +251,34 @@ impl EnemyType {
         Self::DangPtr => 12., 
     } }
 
+    /// 装甲
+    /// 受けるダメージから一律に差し引く
+    pub fn armor(&self) -> f32 { match self {
+        Self::UndefBeh => 0., 
+        Self::NullPtr => 0., 
+        Self::DataRace => 0.25, 
+        Self::DangPtr => 0.75, 
+    }}
+
+    /// ダメージの種類ごとの倍率
+    pub fn resistance(&self, dt: DamageType) -> f32 { match (self, dt) {
+        (Self::UndefBeh, _) => 1., 
+        (Self::NullPtr, DamageType::Kinetic) => 1., 
+        (Self::NullPtr, DamageType::Explosive) => 1.25, 
+        (Self::NullPtr, DamageType::Fragment) => 0.8, 
+        (Self::DataRace, DamageType::Kinetic) => 1., 
+        (Self::DataRace, DamageType::Explosive) => 1., 
+        (Self::DataRace, DamageType::Fragment) => 1.2, 
+        (Self::DangPtr, DamageType::Kinetic) => 0.6, 
+        (Self::DangPtr, DamageType::Explosive) => 1.5, 
+        (Self::DangPtr, DamageType::Fragment) => 0.5, 
+    }}
+
+    /// 装甲と倍率を適用した実際のダメージ
+    pub fn effective_damage(&self, dt: DamageType, damage: f32) -> f32 {
+        (damage - self.armor()).max(0.) * self.resistance(dt)
+    }
+
     pub fn health_diffuse(
         &self
     ) -> Option<std::ops::Range<f32>> { match self {
@@ -168,6 +306,36 @@ impl EnemyType {
         EnemyType::DangPtr => 200,
     }}
 
+    /// 撃破時のドロップ表
+    /// (アイテム, 確率)の組で、確率の合計は1を超えないこと
+    pub fn drop_table(
+        &self, 
+    ) -> &'static [(ItemType, f64)] { match self {
+        EnemyType::UndefBeh => &[
+            (ItemType::Repair, 0.01), 
+            (ItemType::MissileAmmo, 0.02), 
+        ],
+        EnemyType::NullPtr => &[
+            (ItemType::Repair, 0.03), 
+            (ItemType::MissileAmmo, 0.05), 
+            (ItemType::FireRateBoost, 0.02), 
+        ],
+        EnemyType::DataRace => &[
+            (ItemType::Repair, 0.05), 
+            (ItemType::MissileAmmo, 0.08), 
+            (ItemType::FireRateBoost, 0.04), 
+            (ItemType::Shield, 0.03), 
+            (ItemType::WeaponUnlock, 0.01), 
+        ],
+        EnemyType::DangPtr => &[
+            (ItemType::Repair, 0.15), 
+            (ItemType::MissileAmmo, 0.15), 
+            (ItemType::FireRateBoost, 0.1), 
+            (ItemType::Shield, 0.1), 
+            (ItemType::WeaponUnlock, 0.1), 
+        ],
+    }}
+
     pub fn spawn(
         self, 
         ident: EnemyIdent,
@@ -211,6 +379,7 @@ impl EnemyType {
                 )
             }
         );    
+        let anim = crate::game::anim::Animator::new(self.anim_clip());
         Enemy {
             ident,
             killed: false, 
@@ -225,6 +394,11 @@ impl EnemyType {
                 vel * rotation.sin(), 
             ),
             health, 
+            health_max: health, 
+            burn_damage: 0., 
+            burn_popup: 0., 
+            status: StatusSet::new(), 
+            anim, 
         }
     }
 }
@@ -240,6 +414,13 @@ pub struct Enemy {
     vel: f32, 
     velocity: nalgebra::Vector2<f32>, 
     health: f32, 
+    health_max: f32, 
+    /// まだ表示していない燃焼ダメージの合計
+    burn_damage: f32, 
+    /// 燃焼ダメージを最後に表示してからの経過時間
+    burn_popup: f32, 
+    status: StatusSet, 
+    anim: crate::game::anim::Animator, 
 }
 impl Enemy {
     pub fn update(
@@ -249,23 +430,78 @@ impl Enemy {
         _spawner: &mut super::spawn::EnemySpawnerArray, 
         score: &mut u64, 
         health: &mut u64, 
+        shield: &mut u64, 
+        items: &mut crate::game::item::ItemArray, 
+        feedback: &mut crate::game::feedback::Feedback, 
+        particles: &mut crate::game::particle::ParticleSystem, 
+        camera: &mut crate::game::camera_fx::CameraFx, 
     ) -> bool {
+        // 燃焼ダメージは装甲を無視する
+        let burn = self.status.update(cycle);
+        self.health -= burn;
+        // 継続ダメージは毎ティック表示せず、一定時間ごとにまとめて表示する
+        self.burn_damage += burn;
+        self.burn_popup += cycle.dur;
+        if 0. < self.burn_damage && (
+            crate::game::feedback::DOT_POPUP_INTERVAL <= self.burn_popup
+            || self.health <= 0.
+        ) {
+            feedback.push_damage(self.position, self.burn_damage);
+            self.burn_damage = 0.;
+            self.burn_popup = 0.;
+        }
+        let vel = self.vel * self.status.vel_scale();
         self.velocity = [
-            self.vel * self.rotation.cos(), 
-            self.vel * self.rotation.sin(), 
+            vel * self.rotation.cos(), 
+            vel * self.rotation.sin(), 
         ].into();
         self.position += self.velocity * cycle.dur;
-        self.render_rot += self.render_rot_speed * cycle.dur;
+        if !self.status.is_stunned() {
+            self.render_rot += self.render_rot_speed * cycle.dur;
+            self.anim.update(cycle.dur, |ev| match ev {
+                "glitch" => particles.emit(
+                    crate::game::particle::Emitter::HitSpark, 
+                    self.position, 
+                    self.render_rot, 
+                    self.velocity, 
+                ), 
+                _ => {}, 
+            });
+        }
         if self.health <= 0. { self.killed = true }
-        if self.killed { *score += self.enemy_type.score() }
+        if self.killed { 
+            *score += self.enemy_type.score();
+            feedback.push_score(self.position, self.enemy_type.score());
+            particles.emit(
+                crate::game::particle::Emitter::DeathBurst, 
+                self.position, 
+                0., 
+                self.velocity * 0.5, 
+            );
+            if let EnemyType::DangPtr = self.enemy_type {
+                camera.push(crate::game::camera_fx::CameraEvent::DangPtrKill)
+            }
+            if let Some(it) = crate::RNG.with(|r| ItemType::roll(
+                &mut *r.borrow_mut(), 
+                self.enemy_type.drop_table(), 
+            )) {
+                items.push(it.spawn(self.position));
+            }
+        }
         let varea = varea.visible_area();
         let out_of_under = self.position.y < varea[0].y;
         let out_of_varea = self.position.x < varea[0].x
             && varea[1].x < self.position.x
             && varea[1].y < self.position.y;
-        if out_of_under { *health = health.checked_sub(
-            self.enemy_type.damage()
-        ).unwrap_or(0) }
+        if out_of_under { 
+            camera.push(crate::game::camera_fx::CameraEvent::Leak);
+            let damage = self.enemy_type.damage();
+            let absorbed = damage.min(*shield);
+            *shield -= absorbed;
+            *health = health.checked_sub(
+                damage - absorbed
+            ).unwrap_or(0) 
+        }
         !out_of_under
         && !out_of_varea
         && !self.killed
@@ -273,9 +509,38 @@ impl Enemy {
 
     pub fn give_damage(
         &mut self, 
+        dt: DamageType, 
         damage: f32, 
+        feedback: &mut crate::game::feedback::Feedback, 
     ) {
+        let damage = self.enemy_type.effective_damage(dt, damage)
+            * self.status.damage_scale();
         self.health -= damage;
+        feedback.push_damage(self.position, damage);
+    }
+
+    /// 状態異常をかける
+    pub fn apply_status(&mut self, effect: StatusEffect) {
+        self.status.apply(effect)
+    }
+
+    /// かかっている状態異常
+    pub fn status(&self) -> &StatusSet {
+        &self.status
+    }
+
+    /// 残り体力
+    pub fn health(&self) -> f32 {
+        self.health
+    }
+
+    /// 最大体力に対する残り体力の比率
+    pub fn health_ratio(&self) -> f32 {
+        self.health / self.health_max
+    }
+
+    pub fn enemy_type(&self) -> &EnemyType {
+        &self.enemy_type
     }
 }
 impl physic::PhysicBody for Enemy {
@@ -295,17 +560,27 @@ impl physic::PhysicBody for Enemy {
         self.velocity
     }
 }
+impl HasShape for Enemy {
+    fn collider(&self) -> Collider {
+        Collider {
+            position: self.position, 
+            shape: self.enemy_type.shape(self.render_rot), 
+        }
+    }
+}
 impl InstanceGen<ImgObjInstance> for Enemy {
     fn generate(
         &self, 
         instances: &mut simple2d::instance::buffer::InstanceArray<ImgObjInstance>
     ) {
+        let (tex_coord, tex_size) = self.anim.tex_rect()
+            .unwrap_or((self.enemy_type.tex_coord(), self.enemy_type.tex_size()));
         instances.push(ImgObjInstance {
             position: self.position.into(),
             size: self.enemy_type.size().into(),
             rotation: self.render_rot,
-            tex_coord: self.enemy_type.tex_coord(),
-            tex_size: self.enemy_type.tex_size(),
+            tex_coord: crate::atlas::locate("enemy_sprite", tex_coord),
+            tex_size,
             tex_rev: [false, false],
         })
     }
@@ -0,0 +1,79 @@
+//! 敵ごとの交戦状況の登録簿
+//! 毎ティック作り直し、同じ敵へ過剰な火力が集中するのを避けるために使う
+
+use tm_wg_wrapper::{
+    prelude::nalgebra::{Point2, Vector2}, 
+    util::simple2d::physic::PhysicBody, 
+};
+
+use super::{damage::DamageType, enemy::{Enemy, EnemyIdent}};
+
+/// 向きによらず予約する、目標からの距離
+pub const RESERVE_RANGE: f32 = 96.;
+
+/// 予約する最接近距離の、目標の大きさに対する余裕
+pub const RESERVE_MARGIN: f32 = 16.;
+
+/// 予測ダメージを予約するべき位置関係か
+/// 目標の近くにいるか、このまま進めば目標に届くように接近している場合に限る
+pub fn is_committed(
+    position: Point2<f32>, 
+    velocity: Vector2<f32>, 
+    enemy: &Enemy, 
+) -> bool {
+    let d = enemy.position - position;
+    if d.x.powi(2) + d.y.powi(2) <= RESERVE_RANGE.powi(2) { return true }
+    let v = velocity - enemy.velocity();
+    let closing = v.dot(&d);
+    let speed2 = v.x.powi(2) + v.y.powi(2);
+    if closing <= 0. || speed2 <= f32::EPSILON { return false }
+    // 相対速度のまま進んだ時の最接近距離
+    let miss = d - v * (closing / speed2);
+    let reach = enemy.enemy_type().shape(0.).radius() + RESERVE_MARGIN;
+    miss.x.powi(2) + miss.y.powi(2) <= reach.powi(2)
+}
+
+/// 敵一体に対する交戦状況
+#[derive(Clone, Copy, Default)]
+pub struct Engagement {
+    /// 予約された予測ダメージ
+    /// 装甲と倍率を適用した後の値
+    pub reserved: f32, 
+    /// 予約したギアの数
+    pub count: u32, 
+}
+
+/// 交戦の登録簿
+pub struct EngagementRegistry(hashbrown::HashMap<EnemyIdent, Engagement>);
+impl EngagementRegistry {
+    pub fn new() -> Self { Self(hashbrown::HashMap::new()) }
+
+    pub fn clear(&mut self) { self.0.clear() }
+
+    /// 予測ダメージの予約
+    pub fn reserve(
+        &mut self, 
+        enemy: &Enemy, 
+        dt: DamageType, 
+        damage: f32, 
+    ) {
+        let e = self.0.entry(enemy.ident.clone()).or_default();
+        e.reserved += enemy.enemy_type().effective_damage(dt, damage);
+        e.count += 1;
+    }
+
+    /// 敵に対する交戦状況
+    pub fn get(&self, ident: &EnemyIdent) -> Engagement {
+        self.0.get(ident).copied().unwrap_or_default()
+    }
+
+    /// 予約が全て命中した場合の残り体力
+    pub fn expected_health(&self, enemy: &Enemy) -> f32 {
+        enemy.health() - self.get(&enemy.ident).reserved
+    }
+
+    /// 予約だけで撃破が見込めるか
+    pub fn is_doomed(&self, enemy: &Enemy) -> bool {
+        self.expected_health(enemy) <= 0.
+    }
+}
@@ -0,0 +1,109 @@
+//! 敵の位置による一様格子の空間索引
+//! 毎ティック敵の更新後に作り直し、当たり判定や目標選択の候補の絞り込みに使う
+
+use tm_wg_wrapper::util::simple2d::physic::PhysicBody;
+
+use super::*;
+use enemy::{Enemy, EnemyRef};
+use crate::game::shape::HasShape;
+
+/// 格子一マスの大きさ
+pub const CELL_SIZE: f32 = 128.;
+
+/// 格子に登録された敵
+struct GridEntry {
+    enemy: EnemyRef, 
+    min: nalgebra::Point2<f32>, 
+    max: nalgebra::Point2<f32>, 
+}
+
+/// 一様格子の空間索引
+pub struct SpatialGrid {
+    cell: f32, 
+    cells: hashbrown::HashMap<(i32, i32), Vec<GridEntry>>, 
+}
+impl SpatialGrid {
+    pub fn new(cell: f32) -> Self { Self {
+        cell, 
+        cells: hashbrown::HashMap::new(), 
+    }}
+
+    fn cell_of(&self, p: nalgebra::Point2<f32>) -> (i32, i32) {
+        ((p.x / self.cell).floor() as i32, (p.y / self.cell).floor() as i32)
+    }
+
+    /// 索引の再構築
+    pub fn rebuild(&mut self, enemies: &EntityArray<ImgObjInstance, Enemy>) {
+        self.cells.values_mut().for_each(|c| c.clear());
+        for e in enemies.iter() {
+            // 回転した形状も収まるよう、形状を囲む矩形で登録する
+            let half = e.entity.collider().shape.bounds() * 0.5;
+            let min = e.entity.position() - half;
+            let max = e.entity.position() + half;
+            let (cmin, cmax) = (self.cell_of(min), self.cell_of(max));
+            for x in cmin.0..=cmax.0 { for y in cmin.1..=cmax.1 {
+                self.cells.entry((x, y)).or_default().push(GridEntry {
+                    enemy: EnemyRef {
+                        ident: e.entity.ident.clone(), 
+                        idx: e.idx, 
+                    }, 
+                    min, 
+                    max, 
+                })
+            }}
+        }
+    }
+
+    /// 矩形に掛かるマスの敵を、条件で絞り込んで重複なく返す
+    fn collect(
+        &self, 
+        min: nalgebra::Point2<f32>, 
+        max: nalgebra::Point2<f32>, 
+        mut filter: impl FnMut(&GridEntry) -> bool, 
+    ) -> Vec<EnemyRef> {
+        let (cmin, cmax) = (self.cell_of(min), self.cell_of(max));
+        let mut found = Vec::new();
+        for x in cmin.0..=cmax.0 { for y in cmin.1..=cmax.1 {
+            if let Some(cell) = self.cells.get(&(x, y)) {
+                found.extend(cell.iter()
+                    .filter(|e| filter(e))
+                    .map(|e| e.enemy.clone())
+                );
+            }
+        }}
+        found.sort_by_key(|e| e.idx);
+        found.dedup_by_key(|e| e.idx);
+        found
+    }
+
+    /// 矩形と重なる敵
+    pub fn query_aabb(
+        &self, 
+        position: nalgebra::Point2<f32>, 
+        size: nalgebra::Vector2<f32>, 
+    ) -> Vec<EnemyRef> {
+        let half = size * 0.5;
+        let (min, max) = (position - half, position + half);
+        self.collect(min, max, |e| 
+            min.x <= e.max.x && e.min.x <= max.x 
+            && min.y <= e.max.y && e.min.y <= max.y
+        )
+    }
+
+    /// 円と重なる敵
+    pub fn query_radius(
+        &self, 
+        center: nalgebra::Point2<f32>, 
+        radius: f32, 
+    ) -> Vec<EnemyRef> {
+        let r = nalgebra::Vector2::new(radius, radius);
+        self.collect(center - r, center + r, |e| {
+            let nearest = nalgebra::Point2::new(
+                center.x.clamp(e.min.x, e.max.x), 
+                center.y.clamp(e.min.y, e.max.y), 
+            );
+            let d = nearest - center;
+            d.x.powi(2) + d.y.powi(2) <= radius.powi(2)
+        })
+    }
+}
@@ -1,8 +1,12 @@
 use super::*;
 
+pub mod damage;
 pub mod enemy;
+pub mod engage;
+pub mod grid;
 pub mod spawn;
 pub mod spawn_ctrl;
+pub mod status;
 
 pub struct EnemyIdentMaster(u64);
 impl EnemyIdentMaster {
@@ -31,6 +35,11 @@ impl EnemyInstances {
         varea: &simple2d::types::VisibleField, 
         score: &mut u64, 
         health: &mut u64, 
+        shield: &mut u64, 
+        items: &mut crate::game::item::ItemArray, 
+        feedback: &mut crate::game::feedback::Feedback, 
+        particles: &mut crate::game::particle::ParticleSystem, 
+        camera: &mut crate::game::camera_fx::CameraFx, 
     ) {
         self.spctrl.update(
             cycle, 
@@ -48,6 +57,11 @@ impl EnemyInstances {
             &mut self.spawner, 
             score, 
             health, 
+            shield, 
+            items, 
+            feedback, 
+            particles, 
+            camera, 
         );
     }
 
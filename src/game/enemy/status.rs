@@ -0,0 +1,238 @@
+//! 敵にかかる状態異常の実装
+
+use super::*;
+
+/// 燃焼の最大重ね掛け数
+pub const BURN_STACK_MAX: usize = 5;
+
+/// 状態異常の種類
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatusKind {
+    /// 減速
+    Slow, 
+    /// 行動停止
+    Stun, 
+    /// 継続ダメージ
+    Burn, 
+    /// 被ダメージ増加
+    Mark, 
+}
+impl StatusKind {
+    /// 表示用のテクスチャ座標
+    pub fn tex_coord(&self) -> [f32; 2] { match self {
+        StatusKind::Slow => [0., 0.], 
+        StatusKind::Stun => [32., 0.], 
+        StatusKind::Burn => [64., 0.], 
+        StatusKind::Mark => [96., 0.], 
+    }}
+
+    pub fn tex_size(&self) -> [f32; 2] { match self {
+        _ => [32., 32.], 
+    }}
+}
+
+/// 状態異常の効果
+/// magnitudeの意味は種類ごとに異なる
+/// - Slow: 速度の倍率
+/// - Stun: 使わない
+/// - Burn: 毎秒のダメージ
+/// - Mark: 被ダメージの倍率
+#[derive(Clone, Copy)]
+pub struct StatusEffect {
+    pub kind: StatusKind, 
+    pub magnitude: f32, 
+    pub duration: f32, 
+}
+
+/// 敵にかかっている状態異常
+/// 減速・被ダメージ増加は効果の強い方を残して時間を延長し、
+/// 行動停止は時間のみを延長し、燃焼は上限まで個別に重ね掛けする
+#[derive(Default)]
+pub struct StatusSet {
+    slow: Option<(f32, f32)>, 
+    stun: Option<f32>, 
+    burns: Vec<(f32, f32)>, 
+    mark: Option<(f32, f32)>, 
+}
+impl StatusSet {
+    pub fn new() -> Self { Self::default() }
+
+    /// 状態異常をかける
+    pub fn apply(&mut self, effect: StatusEffect) { match effect.kind {
+        StatusKind::Slow => self.slow = Some(match self.slow {
+            Some((m, t)) => (m.min(effect.magnitude), t.max(effect.duration)), 
+            None => (effect.magnitude, effect.duration), 
+        }), 
+        StatusKind::Stun => self.stun = Some(
+            self.stun.map_or(effect.duration, |t| t.max(effect.duration))
+        ), 
+        StatusKind::Burn => if self.burns.len() < BURN_STACK_MAX {
+            self.burns.push((effect.magnitude, effect.duration))
+        } else if let Some(b) = self.burns.iter_mut()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+        {
+            // 上限に達していれば最も残り時間の短いものを置き換える
+            *b = (effect.magnitude, effect.duration)
+        }, 
+        StatusKind::Mark => self.mark = Some(match self.mark {
+            Some((m, t)) => (m.max(effect.magnitude), t.max(effect.duration)), 
+            None => (effect.magnitude, effect.duration), 
+        }), 
+    }}
+
+    /// 時間経過の処理
+    /// このティックで受ける燃焼ダメージを返す
+    pub fn update(&mut self, cycle: &cycle_measure::CycleMeasure) -> f32 {
+        let dt = cycle.dur;
+        let burn = self.burns.iter()
+            .map(|(dps, t)| dps * t.min(dt))
+            .sum();
+
+        self.slow = self.slow.map(|(m, t)| (m, t - dt)).filter(|(_, t)| 0. < *t);
+        self.stun = self.stun.map(|t| t - dt).filter(|t| 0. < *t);
+        self.mark = self.mark.map(|(m, t)| (m, t - dt)).filter(|(_, t)| 0. < *t);
+        self.burns.retain_mut(|(_, t)| {
+            *t -= dt;
+            0. < *t
+        });
+        burn
+    }
+
+    /// 速度の倍率
+    pub fn vel_scale(&self) -> f32 {
+        if self.is_stunned() { 0. } else { self.slow.map_or(1., |(m, _)| m) }
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.stun.is_some()
+    }
+
+    /// 被ダメージの倍率
+    pub fn damage_scale(&self) -> f32 {
+        self.mark.map_or(1., |(m, _)| m)
+    }
+
+    /// かかっている状態異常の種類
+    pub fn active(&self) -> impl Iterator<Item = StatusKind> + '_ {
+        [
+            (StatusKind::Slow, self.slow.is_some()), 
+            (StatusKind::Stun, self.stun.is_some()), 
+            (StatusKind::Burn, !self.burns.is_empty()), 
+            (StatusKind::Mark, self.mark.is_some()), 
+        ].into_iter()
+            .filter(|(_, a)| *a)
+            .map(|(k, _)| k)
+    }
+}
+
+/// 状態異常の色合いを敵に重ねて描画するためのラップ
+pub struct StatusOverlay<'a>(pub &'a EntityArray<ImgObjInstance, enemy::Enemy>);
+impl InstanceGen<ImgObjInstance> for StatusOverlay<'_> {
+    fn generate(
+        &self, 
+        instances: &mut simple2d::instance::buffer::InstanceArray<ImgObjInstance>
+    ) {
+        for e in self.0.iter() {
+            let size = e.entity.enemy_type().size();
+            for kind in e.entity.status().active() {
+                instances.push(ImgObjInstance {
+                    position: e.entity.position.into(), 
+                    size: size.into(), 
+                    rotation: 0., 
+                    tex_coord: crate::atlas::locate("status", kind.tex_coord()), 
+                    tex_size: kind.tex_size(), 
+                    tex_rev: [false, false], 
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn effect(kind: StatusKind, magnitude: f32, duration: f32) -> StatusEffect {
+        StatusEffect { kind, magnitude, duration }
+    }
+
+    fn cycle(dur: f32) -> cycle_measure::CycleMeasure {
+        let mut cycle = cycle_measure::CycleMeasure::new();
+        cycle.dur = dur;
+        cycle
+    }
+
+    #[test]
+    fn slow_keeps_stronger_and_longer() {
+        let mut s = StatusSet::new();
+        s.apply(effect(StatusKind::Slow, 0.5, 1.));
+        // 弱く長い減速は倍率を変えず時間だけ延ばす
+        s.apply(effect(StatusKind::Slow, 0.8, 3.));
+        assert_eq!(s.slow, Some((0.5, 3.)));
+        // 強く短い減速は倍率だけ強める
+        s.apply(effect(StatusKind::Slow, 0.2, 2.));
+        assert_eq!(s.slow, Some((0.2, 3.)));
+        assert_eq!(s.vel_scale(), 0.2);
+    }
+
+    #[test]
+    fn mark_keeps_stronger_and_longer() {
+        let mut s = StatusSet::new();
+        s.apply(effect(StatusKind::Mark, 1.5, 1.));
+        s.apply(effect(StatusKind::Mark, 1.2, 3.));
+        assert_eq!(s.mark, Some((1.5, 3.)));
+        s.apply(effect(StatusKind::Mark, 2., 2.));
+        assert_eq!(s.mark, Some((2., 3.)));
+        assert_eq!(s.damage_scale(), 2.);
+    }
+
+    #[test]
+    fn stun_only_extends() {
+        let mut s = StatusSet::new();
+        s.apply(effect(StatusKind::Stun, 0., 2.));
+        s.apply(effect(StatusKind::Stun, 0., 1.));
+        assert_eq!(s.stun, Some(2.));
+        s.apply(effect(StatusKind::Stun, 0., 3.));
+        assert_eq!(s.stun, Some(3.));
+        assert_eq!(s.vel_scale(), 0.);
+    }
+
+    #[test]
+    fn burn_stacks_up_to_max_then_replaces_shortest() {
+        let mut s = StatusSet::new();
+        for i in 0..BURN_STACK_MAX {
+            s.apply(effect(StatusKind::Burn, 1., 2. + i as f32));
+        }
+        assert_eq!(s.burns.len(), BURN_STACK_MAX);
+
+        s.apply(effect(StatusKind::Burn, 10., 5.));
+        assert_eq!(s.burns.len(), BURN_STACK_MAX);
+        assert!(!s.burns.contains(&(1., 2.)));
+        assert!(s.burns.contains(&(10., 5.)));
+    }
+
+    #[test]
+    fn burn_damage_is_clamped_to_remaining_time() {
+        let mut s = StatusSet::new();
+        s.apply(effect(StatusKind::Burn, 10., 0.25));
+        s.apply(effect(StatusKind::Burn, 4., 2.));
+        // 1つ目は残り時間の0.25秒分だけ与える
+        assert_eq!(s.update(&cycle(0.5)), 10. * 0.25 + 4. * 0.5);
+        assert_eq!(s.burns, vec![(4., 1.5)]);
+        assert_eq!(s.update(&cycle(2.)), 4. * 1.5);
+        assert!(s.burns.is_empty());
+        assert_eq!(s.active().count(), 0);
+    }
+
+    #[test]
+    fn effects_expire() {
+        let mut s = StatusSet::new();
+        s.apply(effect(StatusKind::Slow, 0.5, 1.));
+        s.apply(effect(StatusKind::Stun, 0., 0.5));
+        s.apply(effect(StatusKind::Mark, 1.5, 2.));
+        s.update(&cycle(0.75));
+        assert_eq!(s.active().collect::<Vec<_>>(), vec![StatusKind::Slow, StatusKind::Mark]);
+        s.update(&cycle(0.75));
+        assert_eq!(s.active().collect::<Vec<_>>(), vec![StatusKind::Mark]);
+    }
+}
@@ -0,0 +1,197 @@
+//! 命中時のフィードバック表示の実装
+//! ダメージ量・スコアの浮き上がる数字と、敵の体力ゲージを扱う
+
+use super::*;
+
+/// 浮き上がる数字の表示時間
+pub const POPUP_LIFE: f32 = 0.8;
+
+/// 浮き上がる速度
+pub const POPUP_RISE: f32 = 72.;
+
+/// 燃焼などの継続ダメージをまとめて表示する間隔
+pub const DOT_POPUP_INTERVAL: f32 = 0.25;
+
+/// 表示する最小のダメージ量
+/// 表示上0.0になる値は表示しない
+pub const POPUP_MIN_DAMAGE: f32 = 0.05;
+
+/// 体力ゲージの大きさ
+pub const BAR_SIZE: [f32; 2] = [48., 6.];
+
+/// 浮き上がる数字の種類
+#[derive(Clone, Copy)]
+pub enum PopupKind {
+    /// 与えたダメージ
+    Damage, 
+    /// 撃破時のスコア
+    Score, 
+}
+impl PopupKind {
+    /// 文字の大きさの倍率
+    pub fn size_ratio(&self) -> f32 { match self {
+        PopupKind::Damage => 0.75, 
+        PopupKind::Score => 1., 
+    }}
+}
+
+/// 浮き上がる数字
+pub struct Popup {
+    kind: PopupKind, 
+    text: String, 
+    position: nalgebra::Point2<f32>, 
+    life: f32, 
+}
+impl Popup {
+    pub fn update(&mut self, cycle: &cycle_measure::CycleMeasure) -> bool {
+        self.position.y += POPUP_RISE * cycle.dur;
+        self.life -= cycle.dur;
+        0. < self.life
+    }
+
+    /// 消えかけるにつれて文字を縮めることで薄れていく様子を表す
+    /// 文字列の描画は色・透明度を指定できないため、透明度による消え方は表せない
+    fn size_ratio(&self) -> f32 {
+        let fade = (self.life / (POPUP_LIFE * 0.5)).min(1.);
+        self.kind.size_ratio() * fade
+    }
+}
+
+/// フィードバック表示の管理
+pub struct Feedback {
+    popups: Vec<Popup>, 
+    /// ダメージ量・スコアの表示
+    pub show_numbers: bool, 
+    /// 体力ゲージの表示
+    pub show_bars: bool, 
+    toggle_numbers: Trigger, 
+    toggle_bars: Trigger, 
+}
+impl Feedback {
+    pub fn new() -> Self { Self {
+        popups: Vec::new(), 
+        show_numbers: true, 
+        show_bars: true, 
+        toggle_numbers: Trigger::default(), 
+        toggle_bars: Trigger::default(), 
+    }}
+
+    /// 与えたダメージの表示
+    pub fn push_damage(
+        &mut self, 
+        position: nalgebra::Point2<f32>, 
+        damage: f32, 
+    ) {
+        if !self.show_numbers || damage < POPUP_MIN_DAMAGE { return }
+        self.popups.push(Popup {
+            kind: PopupKind::Damage, 
+            text: if damage < 10. {
+                format!("{:.1}", damage)
+            } else {
+                format!("{:.0}", damage)
+            }, 
+            position, 
+            life: POPUP_LIFE, 
+        })
+    }
+
+    /// 撃破時のスコアの表示
+    pub fn push_score(
+        &mut self, 
+        position: nalgebra::Point2<f32>, 
+        score: u64, 
+    ) {
+        if !self.show_numbers { return }
+        self.popups.push(Popup {
+            kind: PopupKind::Score, 
+            text: format!("+{}", score), 
+            position, 
+            life: POPUP_LIFE * 1.5, 
+        })
+    }
+
+    pub fn update(&mut self, cycle: &cycle_measure::CycleMeasure) {
+        self.toggle_numbers.update();
+        self.toggle_bars.update();
+        if self.toggle_numbers.get_trig_count() == 1 {
+            self.show_numbers = !self.show_numbers;
+            if !self.show_numbers { self.popups.clear() }
+        }
+        if self.toggle_bars.get_trig_count() == 1 {
+            self.show_bars = !self.show_bars;
+        }
+        self.popups.retain_mut(|p| p.update(cycle));
+    }
+
+    pub fn input_key(&mut self, keycode: VirtualKeyCode, state: ElementState) {
+        match keycode {
+            VirtualKeyCode::N => self.toggle_numbers.trigger(state), 
+            VirtualKeyCode::B => self.toggle_bars.trigger(state), 
+            _ => {}, 
+        }
+    }
+
+    pub fn rendering(
+        &self, 
+        renderer: &mut crate::renderer::FSRenderer, 
+        enemies: &enemy::enemy::EnemyArray, 
+    ) {
+        if self.show_bars {
+            renderer.sprite(crate::render_queue::Layer::Effects).push_instance(&HealthBars(&enemies.enemies));
+        }
+        if self.show_numbers {
+            for p in self.popups.iter() {
+                let ratio = p.size_ratio();
+                renderer.text(crate::render_queue::Layer::Effects).draw_type(&simple2d::font_typing::TypeParam {
+                    s: &p.text, 
+                    position: p.position.into(), 
+                    rotation: 0., 
+                    size_ratio: [ratio, ratio], 
+                    align_v: simple2d::font_typing::TypeAlignV::Middle, 
+                    align_h: simple2d::font_typing::TypeAlignH::Center, 
+                    direction: simple2d::font_typing::TypeDirection::Horizontal, 
+                });
+            }
+        }
+    }
+}
+
+/// ダメージを受けた敵の体力ゲージ
+pub struct HealthBars<'a>(pub &'a EntityArray<ImgObjInstance, enemy::enemy::Enemy>);
+impl InstanceGen<ImgObjInstance> for HealthBars<'_> {
+    fn generate(
+        &self, 
+        instances: &mut simple2d::instance::buffer::InstanceArray<ImgObjInstance>
+    ) {
+        for e in self.0.iter() {
+            let ratio = e.entity.health_ratio();
+            if 1. <= ratio { continue }
+            let ratio = ratio.max(0.);
+            let position = e.entity.position
+                + nalgebra::Vector2::new(0., e.entity.enemy_type().size().y * 0.5 + 8.);
+
+            // 背景
+            instances.push(ImgObjInstance {
+                position: position.into(), 
+                size: BAR_SIZE, 
+                rotation: 0., 
+                tex_coord: crate::atlas::locate("bar", [0., 8.]), 
+                tex_size: [16., 8.], 
+                tex_rev: [false, false], 
+            });
+            // 残量は左端に揃える
+            let width = BAR_SIZE[0] * ratio;
+            instances.push(ImgObjInstance {
+                position: [
+                    position.x - (BAR_SIZE[0] - width) * 0.5, 
+                    position.y, 
+                ], 
+                size: [width, BAR_SIZE[1]], 
+                rotation: 0., 
+                tex_coord: crate::atlas::locate("bar", [0., 0.]), 
+                tex_size: [16., 8.], 
+                tex_rev: [false, false], 
+            });
+        }
+    }
+}
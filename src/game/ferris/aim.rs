@@ -1,13 +1,31 @@
 use tm_wg_wrapper::util::simple2d::physic::PhysicBody;
 
-use crate::game::enemy::enemy::EnemyRef;
+use crate::game::{enemy::enemy::EnemyRef, shape};
 
 use super::*;
 
+/// 多目標ロックオンの最大数
+pub const LOCK_MAX: usize = 8;
+
+/// 照準から外れたロックオンを保持する時間
+pub const LOCK_TIME: f32 = 4.;
+
+/// ロックオン中の目標
+#[derive(Clone)]
+pub struct LockOn {
+    pub enemy: EnemyRef, 
+    pub position: nalgebra::Point2<f32>, 
+    pub time_left: f32, 
+}
+
 pub struct Aim {
     pub pbody: AimPhysicBody, 
     visible: bool, 
     pub state: AimState, 
+    pub locks: Vec<LockOn>, 
+    /// ロックオンを離し、一斉射撃の発射を待っている
+    pub salvo: bool, 
+    anim: crate::game::anim::Animator, 
 }
 impl Aim {
     pub fn new() -> Self { Self {
@@ -16,6 +34,9 @@ impl Aim {
         }, 
         visible: true, 
         state: AimState::Normal, 
+        locks: Vec::new(), 
+        salvo: false, 
+        anim: crate::game::anim::Animator::new(AimState::Normal.anim_clip()), 
     }}
 
     pub fn input_mouse_motion(
@@ -27,11 +48,15 @@ impl Aim {
 
     pub fn update(
         &mut self, 
+        cycle: &cycle_measure::CycleMeasure, 
         varea: &VisibleField, 
         ferris: &ferris::Ferris, 
         enemies: &enemy::enemy::EnemyArray, 
+        engage: &enemy::engage::EngagementRegistry, 
         track_trigger: bool, 
         gear_vel: f32, 
+        lock_hold: bool, 
+        lock_release: bool, 
     ) {
         let va = varea.visible_area();
         if self.pbody.position.x < va[0].x {
@@ -46,7 +71,60 @@ impl Aim {
             self.pbody.position.y = va[1].y
         };
 
-        self.state.update(&mut self.pbody, ferris, enemies, track_trigger, gear_vel);
+        self.state.update(&mut self.pbody, ferris, enemies, engage, track_trigger, gear_vel);
+        self.update_locks(cycle, enemies, lock_hold, lock_release, ferris.launched_locks);
+        self.anim.play(self.state.anim_clip());
+        self.anim.update(cycle.dur, |_| {});
+    }
+
+    /// ロックオンの更新
+    /// 押下中は照準に重なった敵を追加し、離すと一斉射撃の発射待ちにする
+    /// 発射待ちのロックオンは、発射されたものから順に外す
+    fn update_locks(
+        &mut self, 
+        cycle: &cycle_measure::CycleMeasure, 
+        enemies: &enemy::enemy::EnemyArray, 
+        lock_hold: bool, 
+        lock_release: bool, 
+        launched: usize, 
+    ) {
+        // 発射したロックオンは先頭から順に並んでいる
+        self.locks.drain(..launched.min(self.locks.len()));
+
+        // 解決できなくなった、もしくは時間切れの目標を外す
+        self.locks.retain_mut(|l| match enemies.get(&l.enemy) {
+            Some(e) => {
+                l.position = e.position;
+                l.time_left -= cycle.dur;
+                0. < l.time_left
+            }, 
+            None => false, 
+        });
+
+        if lock_hold {
+            // 押し直した場合は発射待ちをやめ、ロックオンを続ける
+            self.salvo = false;
+            for (r, e) in enemies.query_aabb(self.pbody.position, self.pbody.size())
+                .into_iter()
+                .filter_map(|r| enemies.get(&r).map(|e| (r, e)))
+                .filter(|(_, e)| shape::overlap(&self.pbody, *e))
+            {
+                if let Some(l) = self.locks.iter_mut()
+                    .find(|l| l.enemy.ident == e.ident)
+                {
+                    l.time_left = LOCK_TIME;
+                } else if self.locks.len() < LOCK_MAX {
+                    self.locks.push(LockOn {
+                        enemy: r, 
+                        position: e.position, 
+                        time_left: LOCK_TIME, 
+                    })
+                }
+            }
+        } else if lock_release {
+            self.salvo = true
+        }
+        if self.locks.is_empty() { self.salvo = false }
     }
 }
 impl InstanceGen<ImgObjInstance> for Aim {
@@ -54,12 +132,14 @@ impl InstanceGen<ImgObjInstance> for Aim {
         &self, 
         instances: &mut simple2d::instance::buffer::InstanceArray<ImgObjInstance>
     ) {
+        let (tex_coord, tex_size) = self.anim.tex_rect()
+            .unwrap_or((self.state.tex_coord(), self.state.tex_size()));
         instances.push(ImgObjInstance {
             position: self.pbody.position.into(),
             size: self.state.size().into(),
             rotation: 0.,
-            tex_coord: self.state.tex_coord(),
-            tex_size: self.state.tex_size(),
+            tex_coord: crate::atlas::locate("aim", tex_coord),
+            tex_size,
             tex_rev: [false, false],
         });
 
@@ -72,19 +152,38 @@ impl InstanceGen<ImgObjInstance> for Aim {
                     position: (self.pbody.position + vec).into(),
                     size: [32., 32.],
                     rotation: 0.,
-                    tex_coord: [0., 128.],
+                    tex_coord: crate::atlas::locate("aim", [0., 128.]),
                     tex_size: [32., 32.],
                     tex_rev: [false, false],
                 })
             }, 
             _ => {}, 
         }
+
+        for lock in self.locks.iter() {
+            instances.push(ImgObjInstance {
+                position: lock.position.into(),
+                size: [48., 48.],
+                rotation: 0.,
+                tex_coord: crate::atlas::locate("aim", [64., 0.]),
+                tex_size: [64., 64.],
+                tex_rev: [false, false],
+            })
+        }
     }
 }
 
 pub struct AimPhysicBody {
     pub position: nalgebra::Point2<f32>, 
 }
+impl shape::HasShape for AimPhysicBody {
+    fn collider(&self) -> shape::Collider {
+        shape::Collider {
+            position: self.position, 
+            shape: shape::Shape::Aabb { size: [64., 64.].into() }, 
+        }
+    }
+}
 impl physic::PhysicBody for AimPhysicBody {
     fn position(&self) -> nalgebra::Point2<f32> {
         self.position
@@ -136,16 +235,27 @@ impl AimState {
         _ => [64., 64.], 
     }}
 
+    /// 再生するアニメーションのクリップ名
+    pub fn anim_clip(&self) -> &'static str { match self {
+        AimState::Normal => "aim_normal", 
+        AimState::Tracking{..} => "aim_tracking", 
+        AimState::TrackReady => "aim_ready", 
+        AimState::Failure => "aim_failure", 
+    }}
+
     pub fn update(
         &mut self, 
         pbody: &mut AimPhysicBody,  
         ferris: &ferris::Ferris, 
         enemies: &enemy::enemy::EnemyArray, 
+        engage: &enemy::engage::EngagementRegistry, 
         track_trigger: bool, 
         vel: f32, 
     ) { match self {
-        AimState::Normal => if let Some(_) = enemies.enemies.iter()
-            .filter(|e| physic::aabb(pbody, e.entity))
+        AimState::Normal => if let Some(_) = enemies.query_aabb(pbody.position, pbody.size())
+            .iter()
+            .filter_map(|r| enemies.get(r))
+            .filter(|e| shape::overlap(pbody, *e))
             .next() {
                 *self = Self::TrackReady;
             },
@@ -163,8 +273,11 @@ impl AimState {
         } else {
             *self = Self::Normal
         },
-        AimState::TrackReady => match enemies.enemies.iter()
-            .filter(|e| physic::aabb(pbody, e.entity))
+        // 既に撃破が見込まれる敵よりも、そうでない敵を優先して捉える
+        AimState::TrackReady => match enemies.query_aabb(pbody.position, pbody.size())
+            .into_iter()
+            .filter_map(|r| enemies.get(&r).map(|e| (r, e)))
+            .filter(|(_, e)| shape::overlap(pbody, *e))
             .fold(
                 None, 
                 |
@@ -173,15 +286,19 @@ impl AimState {
                 | match init {
                     None => Some(e), 
                     Some(ie) => {
+                        let doomed = [
+                            engage.is_doomed(e.1), 
+                            engage.is_doomed(ie.1), 
+                        ];
                         let d = [
-                            pbody.position - e.entity.position(), 
-                            pbody.position - ie.entity.position(), 
+                            pbody.position - e.1.position(), 
+                            pbody.position - ie.1.position(), 
                         ];
                         let d = [
                             f32::sqrt(d[0].x.powi(2) + d[0].y.powi(2)), 
                             f32::sqrt(d[1].x.powi(2) + d[1].y.powi(2)),  
                         ];
-                        if d[1] <= d[0] {
+                        if doomed[1] == doomed[0] && d[1] <= d[0] || doomed[0] && !doomed[1] {
                             Some(ie)
                         } else {
                             Some(e)
@@ -190,17 +307,14 @@ impl AimState {
                 }
             ) {
                 None => *self = Self::Normal, 
-                Some(e) => if track_trigger {
+                Some((enemy, e)) => if track_trigger {
                     let vec = physic::deviation_pos(
                         ferris, 
-                        e.entity, 
+                        e, 
                         vel
                     ) - pbody.position;
                     *self = Self::Tracking { 
-                        enemy: EnemyRef {
-                            ident: e.entity.ident.clone(), 
-                            idx: e.idx, 
-                        }, 
+                        enemy, 
                         vec 
                     }
                 }, 
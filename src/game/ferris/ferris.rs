@@ -25,6 +25,17 @@ impl physic::PhysicBody for FerrisBody {
         self.velocity
     }
 }
+impl crate::game::shape::HasShape for FerrisBody {
+    fn collider(&self) -> crate::game::shape::Collider {
+        crate::game::shape::Collider {
+            position: self.position, 
+            shape: crate::game::shape::Shape::Obb {
+                size: [self.size.x * 0.8, self.size.y * 0.5].into(), 
+                rotation: self.rotation, 
+            }, 
+        }
+    }
+}
 impl FerrisBody {
     pub fn update(
         &mut self, 
@@ -53,6 +64,9 @@ pub struct Ferris {
     pub gg2: ngear::gtype::gun::GearGun, 
     pub ml: ngear::gtype::missile::MissileLauncher, 
     pub rotate_speed: f32, 
+    /// このティックに一斉射撃で発射したロックオンの数
+    pub launched_locks: usize, 
+    anim: crate::game::anim::Animator, 
 }
 impl physic::PhysicBody for Ferris {
     fn position(&self) -> nalgebra::Point2<f32> {
@@ -76,12 +90,14 @@ impl InstanceGen<ImgObjInstance> for Ferris {
         &self, 
         instances: &mut simple2d::instance::buffer::InstanceArray<ImgObjInstance>
     ) {
+        let (tex_coord, tex_size) = self.anim.tex_rect()
+            .unwrap_or(([0., 0.], [64., 64.]));
         instances.push(ImgObjInstance { 
             position: self.body.position.into(), 
             size: self.body.size.into(), 
             rotation: self.body.rotation, 
-            tex_coord: [0., 0.], 
-            tex_size: [64., 64.], 
+            tex_coord: crate::atlas::locate("ferris", tex_coord), 
+            tex_size, 
             tex_rev: [false, false], 
         })
     }
@@ -98,6 +114,8 @@ impl Ferris {
         gg2: ngear::gtype::gun::GearGun::default(), 
         ml: ngear::gtype::missile::MissileLauncher::default(), 
         rotate_speed: 360., 
+        launched_locks: 0, 
+        anim: crate::game::anim::Animator::new("ferris_idle"), 
     }}
 
     pub fn update(
@@ -127,7 +145,7 @@ impl Ferris {
             _ => 0., 
         } * (std::f32::consts::PI / 180.) * cycle.dur;
 
-        if self.control.shoot_kb.is_triggered() || self.control.shoot_mb.is_triggered() {
+        let shot = if self.control.shoot_kb.is_triggered() || self.control.shoot_mb.is_triggered() {
             self.gg2.shoot(
                 &self.body, 
                 gears2, 
@@ -144,18 +162,43 @@ impl Ferris {
                         }
                     )
                 }, 
-            );
+                aim.and_then(|aim| match &aim.state {
+                    aim::AimState::Tracking { enemy, .. } => Some(enemy), 
+                    _ => None, 
+                }), 
+            )
+        } else { false };
+
+        // 発射の反動
+        if shot {
+            self.anim.play("ferris_shoot");
+            self.anim.restart();
         }
+        self.anim.update(cycle.dur, |_| {});
+        if self.anim.is_finished() {
+            self.anim.play("ferris_idle")
+        }
+
         if self.control.sg_ch.get_trig_count() == 1 { match self.control.sg_ch.get_mode() {
-            RevMode::Forward => self.gg2.gt.toggle(
+            RevMode::Forward => self.gg2.toggle(
                 crate::game::ferris::ngear::gtype::gun::GTToggle::Forward
             ),
-            RevMode::Backward => self.gg2.gt.toggle(
+            RevMode::Backward => self.gg2.toggle(
                 crate::game::ferris::ngear::gtype::gun::GTToggle::Backward
             ),
             _ => {}, 
         }}
 
+        if self.control.ms_ch.get_trig_count() == 1 {
+            self.ml.toggle(
+                crate::game::ferris::ngear::gtype::gun::GTToggle::Forward
+            )
+        }
+
+        if self.control.reload.get_trig_count() == 1 {
+            self.gg2.reload()
+        }
+
         if self.control.shoot_ms.is_triggered() {
             self.ml.shoot(
                 &self.body, 
@@ -164,6 +207,16 @@ impl Ferris {
             )
         }
 
+        // 発射待ちの一斉射撃は、発射できるようになり次第撃つ
+        self.launched_locks = match aim {
+            Some(aim) if aim.salvo => self.ml.shoot_locks(
+                &self.body, 
+                &aim.locks, 
+                gears2
+            ), 
+            _ => 0, 
+        };
+
         if let Some(aim) = aim { if !self.control.manual_track.is_latch_on() {
             let angle = {
                 let distance: Vector2<f32> = self.body.position - (aim.pbody.position + if let aim::AimState::Tracking { 
@@ -206,7 +259,15 @@ pub struct Control {
     pub auto_aim: Trigger, 
     pub manual_track: Latch, 
     pub time_fuze: Trigger, 
-
+    /// 再装填
+    pub reload: Trigger, 
+    /// 撃つミサイルの切り替え
+    pub ms_ch: Trigger, 
+    /// 多目標ロックオン
+    pub lock_on: Trigger, 
+    lock_prev: bool, 
+    /// ロックオンを離した瞬間
+    pub lock_release: bool, 
 }
 impl Control {
     pub fn input_key(
@@ -223,9 +284,10 @@ impl Control {
         VirtualKeyCode::Z => self.sg_ch.input(RevMode::Backward, state), 
         VirtualKeyCode::X => self.manual_track.trigger(state), 
         VirtualKeyCode::C => self.sg_ch.input(RevMode::Forward, state), 
-        VirtualKeyCode::R => {}, 
+        VirtualKeyCode::R => self.reload.trigger(state), 
+        VirtualKeyCode::T => self.ms_ch.trigger(state), 
         VirtualKeyCode::F => self.shoot_ms.trigger(state), 
-        VirtualKeyCode::V => {}, 
+        VirtualKeyCode::V => self.lock_on.trigger(state), 
         VirtualKeyCode::Space => self.shoot_kb.trigger(state), 
         VirtualKeyCode::G => self.time_fuze.trigger(state), 
         _ => {}, 
@@ -262,5 +324,11 @@ impl Control {
         self.auto_aim.update();
         self.manual_track.update();
         self.time_fuze.update();
+        self.reload.update();
+        self.ms_ch.update();
+        self.lock_on.update();
+        let lock_now = self.lock_on.is_triggered();
+        self.lock_release = self.lock_prev && !lock_now;
+        self.lock_prev = lock_now;
     }
 }
\ No newline at end of file
@@ -2,7 +2,6 @@ use super::*;
 
 pub mod ferris;
 pub mod aim;
-//pub mod gear;
 pub mod phys;
 pub mod ngear;
 use ngear::gtype::GTypeTrait;
@@ -11,19 +10,28 @@ pub struct FerrisInstances {
     ferris: EntityHolder<ImgObjInstance, ferris::Ferris>, 
     aim: EntityHolder<ImgObjInstance, aim::Aim>, 
     gear2: ngear::array::GearInstances, 
+    engage: enemy::engage::EngagementRegistry, 
 }
 impl FerrisInstances {
     pub fn new() -> Self { Self {
         ferris: EntityHolder::new(ferris::Ferris::new()), 
         aim: EntityHolder::new(aim::Aim::new()), 
         gear2: ngear::array::GearInstances::new(), 
+        engage: enemy::engage::EngagementRegistry::new(), 
     }}
 
+    pub fn ferris(&self) -> Option<&ferris::Ferris> {
+        self.ferris.get()
+    }
+
     pub fn update(
         &mut self, 
         cycle: &cycle_measure::CycleMeasure, 
         varea: &simple2d::types::VisibleField, 
         enemies: &mut enemy::enemy::EnemyArray, 
+        particles: &mut particle::ParticleSystem, 
+        camera: &mut camera_fx::CameraFx, 
+        feedback: &mut feedback::Feedback, 
     ) {
         self.ferris.manip_mut(|f| f.update(
             cycle, 
@@ -36,26 +44,44 @@ impl FerrisInstances {
             varea, 
             self.ferris.get().map(|f| &f.body), 
             &self.aim, 
-            enemies
+            enemies, 
+            &mut self.engage, 
+            particles, 
+            camera, 
+            feedback, 
         );
         if let Some(ferris) = self.ferris.get() {
             self.aim.manip_mut(|a| a.update (
+                cycle, 
                 varea, 
                 ferris, 
                 enemies, 
+                &self.engage, 
                 ferris.control.auto_aim.get_trig_count() == 1, 
                 ferris.gg2.gt.shoot_shell().vel_default(), 
+                ferris.control.lock_on.is_triggered(), 
+                ferris.control.lock_release, 
             ));
         }
     }
 
+    /// 接触したアイテムの回収
+    pub fn collect_items(
+        &mut self, 
+        items: &mut item::ItemArray, 
+        health: &mut u64, 
+        shield: &mut u64, 
+    ) {
+        self.ferris.manip_mut(|f| items.collect(f, health, shield));
+    }
+
     pub fn rendering(
         &self, 
         renderer: &mut crate::renderer::FSRenderer, 
     ) {
-        renderer.ferris.push_instance(&self.ferris);
+        renderer.sprite(crate::render_queue::Layer::Player).push_instance(&self.ferris);
         self.gear2.rendering(renderer);
-        renderer.aim.push_instance(&self.aim);
+        renderer.sprite(crate::render_queue::Layer::Hud).push_instance(&self.aim);
     }
 
     pub fn input_key(&mut self, keycode: VirtualKeyCode, state: ElementState) {
@@ -55,6 +55,11 @@ impl GearInstances {
         }
     }
 
+    /// ギアコマンドの追加
+    pub fn push_comm(&mut self, gcomm: super::gcomm::GComm) {
+        self.gcomm.push(gcomm)
+    }
+
     pub fn update(
         &mut self, 
         cycle: &cycle_measure::CycleMeasure, 
@@ -64,6 +69,10 @@ impl GearInstances {
             ImgObjInstance, crate::game::ferris::aim::Aim, 
         >, 
         enemies: &mut crate::game::enemy::enemy::EnemyArray, 
+        engage: &mut crate::game::enemy::engage::EngagementRegistry, 
+        particles: &mut crate::game::particle::ParticleSystem, 
+        camera: &mut crate::game::camera_fx::CameraFx, 
+        feedback: &mut crate::game::feedback::Feedback, 
     ) {
         self.gcomm.execute(
             &mut self.ident, 
@@ -71,7 +80,24 @@ impl GearInstances {
             ferris, 
             aim.get(), 
             enemies, 
+            particles, 
+            camera, 
+            feedback, 
         );
+
+        // 生存しているギアから交戦の登録簿を作り直す
+        // 目標へ届く見込みのあるギアだけを予約する
+        engage.clear();
+        self.gears.iter()
+            .filter_map(|g| g.entity.gb.gt.engagement().map(|e| (&g.entity, e)))
+            .filter_map(|(g, (t, dt, damage))| enemies.get(t).map(|e| (g, e, dt, damage)))
+            .filter(|(g, e, _, _)| crate::game::enemy::engage::is_committed(
+                g.position(), 
+                g.velocity(), 
+                e, 
+            ))
+            .for_each(|(_, e, dt, damage)| engage.reserve(e, dt, damage));
+
         self.gears.retain(|_, gear| gear.update(
             cycle, 
             varea, 
@@ -79,6 +105,8 @@ impl GearInstances {
             aim, 
             enemies, 
             &mut self.gcomm, 
+            engage, 
+            feedback, 
         ));
     }
 
@@ -86,7 +114,7 @@ impl GearInstances {
         &self, 
         renderer: &mut crate::renderer::FSRenderer, 
     ) {
-        renderer.gear.push_instance(&self.gears)
+        renderer.sprite(crate::render_queue::Layer::PlayerGears).push_instance(&self.gears)
     }
 }
 
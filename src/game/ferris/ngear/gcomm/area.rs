@@ -0,0 +1,70 @@
+use tm_wg_wrapper::{
+    prelude::nalgebra::{Vector2, Point2}, 
+    util::simple2d::physic::PhysicBody, 
+};
+
+use crate::game::{
+    enemy::{enemy::EnemyArray, damage::DamageType}, 
+    particle::{Emitter, ParticleSystem}, 
+};
+
+/// 破片のパーティクルを1回散らす範囲の半径
+pub const DEBRIS_RADIUS_STEP: f32 = 48.;
+
+/// 範囲ダメージ能力
+/// 範囲内の全ての敵に対し、距離に応じて減衰するダメージを即座に与える
+pub struct AreaDamageParam {
+    /// 効果範囲の半径
+    pub radius: f32, 
+    /// 中心でのダメージ
+    pub damage: f32, 
+    /// 範囲の縁でのダメージの比率
+    pub edge_ratio: f32, 
+}
+impl AreaDamageParam {
+    /// 中心からの距離に応じたダメージ
+    pub fn damage_at(&self, dist: f32) -> f32 {
+        if self.radius < dist {
+            0.
+        } else {
+            let r = dist / self.radius;
+            self.damage * (1. - (1. - self.edge_ratio) * r)
+        }
+    }
+
+    /// 破片のパーティクルを散らす回数
+    /// 破片はギアとしては生成せず、上限のあるパーティクルとして扱う
+    pub fn debris_bursts(&self) -> u32 {
+        (self.radius / DEBRIS_RADIUS_STEP).ceil() as u32
+    }
+
+    pub fn apply(
+        self, 
+        enemies: &mut EnemyArray, 
+        feedback: &mut crate::game::feedback::Feedback, 
+        particles: &mut ParticleSystem, 
+        position: Point2<f32>, 
+        base_vel: Vector2<f32>, 
+    ) {
+        for enemy in enemies.query_radius(position, self.radius) { if let Some(
+            enemy
+        ) = enemies.get_mut(&enemy) {
+            // 敵の矩形の中で最も近い点までの距離
+            let half = enemy.size() * 0.5;
+            let center = enemy.position();
+            let nearest = Point2::new(
+                position.x.clamp(center.x - half.x, center.x + half.x), 
+                position.y.clamp(center.y - half.y, center.y + half.y), 
+            );
+            let dist = nearest - position;
+            let dist = (dist.x.powi(2) + dist.y.powi(2)).sqrt();
+            if dist <= self.radius {
+                enemy.give_damage(DamageType::Explosive, self.damage_at(dist), feedback);
+            }
+        }}
+
+        for _ in 0..self.debris_bursts() {
+            particles.emit(Emitter::Debris, position, 0., base_vel)
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use tm_wg_wrapper::{
+    prelude::nalgebra::{Vector2, Point2}, 
+    util::simple2d::{
+        entity_holder::EntityArray, 
+        img_obj::ImgObjInstance, 
+    }
+};
+
+use crate::game::ferris::ngear::{
+    GearInstance, 
+    array::GearIdentMaster, 
+    gtype::{
+        GTypeTrait, 
+        gun::{GunGear, GunGearType}, 
+    }, 
+};
+
+/// 子弾の散布能力
+pub struct ClusterParam {
+    pub count: u32, 
+    pub spread: f32, 
+    pub fuze_base: f32, 
+    pub fuze_diff: Option<std::ops::Range<f32>>, 
+    pub bomblet: GunGearType, 
+}
+impl ClusterParam {
+    pub fn release(
+        self, 
+        ident: &mut GearIdentMaster, 
+        rng: &mut impl rand::Rng, 
+        gears: &mut EntityArray<
+            ImgObjInstance, 
+            GearInstance, 
+        >, 
+        position: Point2<f32>, 
+        rotation: f32, 
+        base_vel: Vector2<f32>, 
+    ) {
+        for _ in 0..self.count {
+            let angle = rotation + rng.gen_range(
+                -self.spread..self.spread
+            );
+            let phys = self.bomblet.vel_calc(
+                rng, 
+                position, 
+                angle, 
+                base_vel, 
+            );
+            let fuze_time = self.fuze_base + self.fuze_diff.clone()
+                .map(|fd| rng.gen_range(fd))
+                .unwrap_or(0.);
+            let gb = super::super::GearBody {
+                phys, 
+                tex_rot_speed: self.bomblet.calc_tex_rot(rng), 
+                tex_rot: 0., 
+                gt: super::super::gtype::GType::GunShot(
+                    GunGear::new(self.bomblet.clone(), Some(fuze_time), None)
+                ),
+            };
+            let gear = GearInstance {
+                ident: ident.issue(),
+                gb,
+            };
+            gears.push(gear);
+        }
+    }
+}
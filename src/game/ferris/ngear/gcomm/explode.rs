@@ -67,6 +67,7 @@ impl ExplodeParam {
             let gb = super::super::GearBody {
                 phys: super::super::GearPhys {
                     position,
+                    prev_position: position, 
                     rotation: fva,
                     vel_a: fv0,
                 },
@@ -8,6 +8,8 @@ use tm_wg_wrapper::{
 
 use super::array::GearIdentMaster;
 pub mod explode;
+pub mod cluster;
+pub mod area;
 
 /// ギアのインスタンス特殊操作用コマンド
 pub enum GComm {
@@ -16,6 +18,24 @@ pub enum GComm {
         position: Point2<f32>, 
         base_vel: Vector2<f32>, 
     }, 
+    Cluster{
+        param: cluster::ClusterParam, 
+        position: Point2<f32>, 
+        rotation: f32, 
+        base_vel: Vector2<f32>, 
+    }, 
+    AreaDamage{
+        param: area::AreaDamageParam, 
+        position: Point2<f32>, 
+        base_vel: Vector2<f32>, 
+    }, 
+    /// 見た目だけのパーティクルの発生
+    Particle{
+        emitter: crate::game::particle::Emitter, 
+        position: Point2<f32>, 
+        rotation: f32, 
+        base_vel: Vector2<f32>, 
+    }, 
 }
 impl GComm {
     pub fn execute(
@@ -27,7 +47,10 @@ impl GComm {
         >, 
         _ferris: Option<&super::super::ferris::FerrisBody>, 
         _aim: Option<&super::super::aim::Aim>, 
-        _enemies: &super::super::super::enemy::enemy::EnemyArray, 
+        enemies: &mut super::super::super::enemy::enemy::EnemyArray, 
+        particles: &mut crate::game::particle::ParticleSystem, 
+        camera: &mut crate::game::camera_fx::CameraFx, 
+        feedback: &mut crate::game::feedback::Feedback, 
     ) { match self {
         GComm::Explode {
             param, 
@@ -42,6 +65,46 @@ impl GComm {
                 base_vel
             )
         ),
+        GComm::Cluster {
+            param, 
+            position, 
+            rotation, 
+            base_vel, 
+        } => crate::RNG.with(
+            |r| param.release(
+                ident, 
+                &mut *r.borrow_mut(), 
+                gears, 
+                position, 
+                rotation, 
+                base_vel
+            )
+        ),
+        GComm::AreaDamage {
+            param, 
+            position, 
+            base_vel, 
+        } => {
+            camera.push_explosion(param.radius);
+            param.apply(
+                enemies, 
+                feedback, 
+                particles, 
+                position, 
+                base_vel
+            )
+        },
+        GComm::Particle {
+            emitter, 
+            position, 
+            rotation, 
+            base_vel, 
+        } => particles.emit(
+            emitter, 
+            position, 
+            rotation, 
+            base_vel
+        ), 
     }}
 }
 
@@ -61,7 +124,10 @@ impl GCommQueue {
         >, 
         ferris: Option<&super::super::ferris::FerrisBody>, 
         aim: Option<&super::super::aim::Aim>, 
-        enemies: &super::super::super::enemy::enemy::EnemyArray, 
+        enemies: &mut super::super::super::enemy::enemy::EnemyArray, 
+        particles: &mut crate::game::particle::ParticleSystem, 
+        camera: &mut crate::game::camera_fx::CameraFx, 
+        feedback: &mut crate::game::feedback::Feedback, 
     ) {
         self.0.iter_mut()
             .filter_map(|gc| gc.take())
@@ -70,7 +136,10 @@ impl GCommQueue {
                 gears, 
                 ferris, 
                 aim, 
-                enemies
+                enemies, 
+                particles, 
+                camera, 
+                feedback, 
             ));
         self.0.clear();
     }
@@ -2,14 +2,11 @@
 
 use tm_wg_wrapper::{
     prelude::*, 
-    util::simple2d::{
-        entity_holder::EntityRefMut, 
-        physic::aabb, 
-    }
 };
 use crate::game::{
     ferris::ngear::GPhysWrap, 
-    enemy::enemy::Enemy, 
+    shape::overlap, 
+    enemy::damage::DamageType, 
 };
 use super::GTypeTrait;
 
@@ -54,23 +51,26 @@ impl GTypeTrait for FragmentGear {
         >, 
         enemies: &mut crate::game::enemy::enemy::EnemyArray, 
         gcomm: &mut super::super::gcomm::GCommQueue, 
+        _engage: &mut crate::game::enemy::engage::EngagementRegistry, 
+        feedback: &mut crate::game::feedback::Feedback, 
     ) -> bool {
         self.life_time -= cycle.dur;
-        for enemy in enemies.enemies.iter_mut() {
-            if aabb(&GPhysWrap {
+        let hit = enemies.query_aabb(phys.position, self.size.into())
+            .into_iter()
+            .find(|r| enemies.get(r).map_or(false, |e| overlap(&GPhysWrap {
                 gt: &super::GType::Fragment(self.clone()),
                 phys,
-            }, enemy.entity) {
-                let damage = {
-                    let base = (
-                        self.size[0]
-                        + self.size[1]
-                    ).sqrt() * 0.1;
-                    base * self.damage_r
-                };
-                enemy.entity.give_damage(damage);
-                return false
-            }
+            }, e)));
+        if let Some(enemy) = hit.and_then(|r| enemies.get_mut(&r)) {
+            let damage = {
+                let base = (
+                    self.size[0]
+                    + self.size[1]
+                ).sqrt() * 0.1;
+                base * self.damage_r
+            };
+            enemy.give_damage(DamageType::Fragment, damage, feedback);
+            return false
         }
         0. < self.life_time 
         && varea.in_visible(phys.position, self.size)
@@ -1,9 +1,14 @@
 //! 砲タイプのギア
 
 use tm_wg_wrapper::{
-    prelude::*, util::simple2d::{entity_holder::EntityRefMut, physic::aabb}, 
+    prelude::*, 
+    util::simple2d::physic::PhysicBody, 
+};
+use crate::game::{
+    ferris::ngear::{GearPhys, gcomm::{explode::ExplodeParam, area::AreaDamageParam, GComm}}, 
+    enemy::{damage::DamageType, status::{StatusEffect, StatusKind}}, 
+    shape::{Collider, HasShape, Shape}, 
 };
-use crate::game::{ferris::ngear::{GPhysWrap, gcomm::{explode::ExplodeParam, GComm}}, enemy::enemy::Enemy};
 
 use super::GTypeTrait;
 
@@ -15,6 +20,7 @@ pub enum GTToggle {
 }
 
 /// ギアを発射する砲の形式
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum GunType {
     ShotGun, 
     GutlingGun, 
@@ -24,6 +30,16 @@ pub enum GunType {
     MiddleRifle, 
 }
 impl GunType {
+    /// 全ての砲の形式
+    pub const ALL: [GunType; 6] = [
+        GunType::ShotGun, 
+        GunType::GutlingGun, 
+        GunType::MachineGun, 
+        GunType::MachineRifle, 
+        GunType::LightRifle, 
+        GunType::MiddleRifle, 
+    ];
+
     pub fn toggle(
         &mut self, 
         gt_toggle: GTToggle, 
@@ -55,6 +71,60 @@ impl GunType {
         GunType::MiddleRifle => 1.5, 
     }}
 
+    /// 表示用の名前
+    pub fn name(&self) -> &'static str { match self {
+        GunType::ShotGun => "SHOTGUN", 
+        GunType::GutlingGun => "GATLING GUN", 
+        GunType::MachineGun => "MACHINE GUN", 
+        GunType::MachineRifle => "MACHINE RIFLE", 
+        GunType::LightRifle => "LIGHT RIFLE", 
+        GunType::MiddleRifle => "MIDDLE RIFLE", 
+    }}
+
+    /// 状態配列の添字
+    pub fn index(&self) -> usize { match self {
+        GunType::ShotGun => 0,
+        GunType::GutlingGun => 1,
+        GunType::MachineGun => 2,
+        GunType::MachineRifle => 3,
+        GunType::LightRifle => 4,
+        GunType::MiddleRifle => 5, 
+    }}
+
+    /// 弾倉の設定
+    /// 弾倉を持たない砲はNone
+    pub fn magazine(&self) -> Option<MagazineParam> { match self {
+        GunType::ShotGun => Some(MagazineParam { size: 8, reload_time: 2. }),
+        GunType::GutlingGun => None,
+        GunType::MachineGun => Some(MagazineParam { size: 200, reload_time: 3. }),
+        GunType::MachineRifle => Some(MagazineParam { size: 60, reload_time: 2.5 }),
+        GunType::LightRifle => Some(MagazineParam { size: 10, reload_time: 2. }),
+        GunType::MiddleRifle => Some(MagazineParam { size: 5, reload_time: 3. }), 
+    }}
+
+    /// 砲身加熱の設定
+    /// 連射向けの砲のみ加熱する
+    pub fn heat(&self) -> Option<HeatParam> { match self {
+        GunType::ShotGun => None,
+        GunType::GutlingGun => Some(HeatParam { 
+            heat_per_shot: 1. / 180., 
+            cool_rate: 0.4, 
+            recover: 0.3, 
+        }),
+        GunType::MachineGun => Some(HeatParam { 
+            heat_per_shot: 1. / 252., 
+            cool_rate: 0.35, 
+            recover: 0.25, 
+        }),
+        GunType::MachineRifle => Some(HeatParam { 
+            heat_per_shot: 1. / 96., 
+            cool_rate: 0.3, 
+            recover: 0.3, 
+        }),
+        GunType::LightRifle => None,
+        GunType::MiddleRifle => None, 
+    }}
+
     pub fn shoot_count(&self) -> u32 { match self {
         GunType::ShotGun => 40,
         GunType::GutlingGun => 3,
@@ -79,6 +149,7 @@ impl GunType {
         &self, 
         ferris: &crate::game::ferris::ferris::FerrisBody, 
         fuze_time: Option<f32>, 
+        target: Option<crate::game::enemy::enemy::EnemyRef>, 
     ) -> super::super::GearBody {
         let gt = self.shoot_shell();
         let (phys, tex_rot_speed) = crate::RNG.with(|r| {
@@ -96,51 +167,244 @@ impl GunType {
             phys,
             tex_rot_speed,
             tex_rot: 0.,
-            gt: super::GType::GunShot(GunGear {
-                ty: gt, 
+            gt: super::GType::GunShot(GunGear::new(
+                gt, 
                 fuze_time, 
-            }),
+                target, 
+            )),
         }
     }
 }
 
+/// 弾倉の設定
+#[derive(Clone, Copy)]
+pub struct MagazineParam {
+    /// 装弾数(射撃回数)
+    pub size: u32, 
+    /// 再装填にかかる時間
+    pub reload_time: f32, 
+}
+
+/// 砲身加熱の設定
+/// 熱量は0.0から1.0で扱い、1.0に達すると過熱する
+#[derive(Clone, Copy)]
+pub struct HeatParam {
+    /// 一射撃あたりの加熱量
+    pub heat_per_shot: f32, 
+    /// 毎秒の冷却量
+    pub cool_rate: f32, 
+    /// 過熱状態から復帰する熱量
+    pub recover: f32, 
+}
+
+/// 砲ごとの弾倉・加熱の状態
+#[derive(Clone)]
+pub struct GunState {
+    /// 弾倉の残弾(弾倉を持たない砲ではNone)
+    pub ammo: Option<u32>, 
+    /// 再装填の残り時間
+    pub reload: Option<f32>, 
+    /// 砲身の熱量
+    pub heat: f32, 
+    /// 過熱による強制冷却中
+    pub overheat: bool, 
+}
+impl GunState {
+    pub fn new(gt: GunType) -> Self { Self {
+        ammo: gt.magazine().map(|m| m.size), 
+        reload: None, 
+        heat: 0., 
+        overheat: false, 
+    }}
+
+    /// 射撃可能か
+    pub fn can_shoot(&self) -> bool {
+        self.reload.is_none()
+        && !self.overheat
+        && self.ammo.map_or(true, |a| 0 < a)
+    }
+}
+
 /// ギアを発射する砲
 pub struct GearGun {
     pub gt: GunType, 
     ct: f32, 
+    states: [GunState; 6], 
+    /// 解禁済みの砲
+    unlocked: [bool; 6], 
+    /// 連射速度上昇の残り時間
+    boost: f32, 
 }
 impl Default for GearGun {
     fn default() -> Self {
-        Self { gt: GunType::ShotGun, ct: 0. }
+        Self { 
+            gt: GunType::ShotGun, 
+            ct: 0., 
+            states: GunType::ALL.map(GunState::new), 
+            unlocked: [true; 6], 
+            boost: 0., 
+        }
     }
 }
 impl GearGun {
 
     /// 射撃処理
+    /// 実際に発射した場合はtrueを返す
     pub fn shoot(
         &mut self, 
         ferris: &crate::game::ferris::ferris::FerrisBody, 
         gears: &mut super::super::array::GearInstances, 
         fuze_time: Option<f32>, 
-    ) { if self.ct == 0.0 {
+        target: Option<&crate::game::enemy::enemy::EnemyRef>, 
+    ) -> bool { if self.ct == 0.0 && self.states[self.gt.index()].can_shoot() {
         for _ in 0..self.gt.shoot_count() {
-            let gear = self.gt.shoot(ferris, fuze_time);
+            let gear = self.gt.shoot(ferris, fuze_time, target.cloned());
             gears.push_gb(gear);
         }
+        let dir = ferris.rotation + std::f32::consts::PI * 0.5;
+        gears.push_comm(GComm::Particle {
+            emitter: crate::game::particle::Emitter::MuzzleFlash, 
+            position: ferris.position + nalgebra::Vector2::new(
+                dir.cos() * ferris.size.y * 0.5, 
+                dir.sin() * ferris.size.y * 0.5, 
+            ), 
+            rotation: dir, 
+            base_vel: ferris.velocity, 
+        });
 
-        self.ct += self.gt.cool_time();
+        self.ct += self.gt.cool_time() * if 0. < self.boost { 0.5 } else { 1. };
+
+        let gt = self.gt;
+        let state = &mut self.states[gt.index()];
+        if let Some(ammo) = state.ammo.as_mut() {
+            *ammo = ammo.saturating_sub(1);
+            if *ammo == 0 {
+                state.reload = gt.magazine().map(|m| m.reload_time);
+            }
+        }
+        if let Some(hp) = gt.heat() {
+            state.heat += hp.heat_per_shot;
+            if 1. <= state.heat {
+                state.heat = 1.;
+                state.overheat = true;
+            }
+        }
+        true
+    } else {
+        false
     }}
 
+    /// 再装填処理
+    /// 弾倉が満杯でなく、再装填中でなければ再装填を始めます
+    pub fn reload(&mut self) {
+        let gt = self.gt;
+        let state = &mut self.states[gt.index()];
+        if let (Some(mag), Some(ammo)) = (gt.magazine(), state.ammo) {
+            if state.reload.is_none() && ammo < mag.size {
+                state.reload = Some(mag.reload_time);
+            }
+        }
+    }
+
+    /// 解禁済みの砲の中で切り替える
+    pub fn toggle(&mut self, gt_toggle: GTToggle) {
+        self.gt = self.neighbor(gt_toggle);
+    }
+
+    /// 切り替えた時に構える砲
+    pub fn neighbor(&self, gt_toggle: GTToggle) -> GunType {
+        let mut gt = self.gt;
+        loop {
+            gt.toggle(gt_toggle.clone());
+            if self.unlocked[gt.index()] { break }
+        }
+        gt
+    }
+
+    /// 未解禁の砲を一つ解禁する
+    /// 全て解禁済みであればfalseを返します
+    pub fn unlock_next(&mut self) -> bool {
+        if let Some(u) = self.unlocked.iter_mut().find(|u| !**u) {
+            *u = true;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// 全ての砲の弾倉を満たし、砲身を冷ます
+    pub fn resupply(&mut self) {
+        self.states = GunType::ALL.map(GunState::new);
+    }
+
+    /// 砲が解禁済みか
+    pub fn is_unlocked(&self, gt: GunType) -> bool {
+        self.unlocked[gt.index()]
+    }
+
+    /// 連射速度上昇を与える
+    pub fn give_boost(&mut self, time: f32) {
+        self.boost = self.boost.max(time);
+    }
+
+    /// 連射速度上昇の残り時間
+    pub fn boost(&self) -> f32 {
+        self.boost
+    }
+
+    /// 現在の砲の状態
+    pub fn state(&self) -> &GunState {
+        &self.states[self.gt.index()]
+    }
+
+    /// 現在のクールタイム
+    pub fn ct(&self) -> f32 {
+        self.ct
+    }
+
     /// 更新処理
-    /// クールタイムの計算をします
+    /// クールタイム・再装填・冷却の計算をします
     pub fn update(
         &mut self, 
         cycle: &cycle_measure::CycleMeasure, 
-    ) { if 0. < self.ct {
-        self.ct -= cycle.dur
-    } else {
-        self.ct = 0.
-    }}
+    ) { 
+        if 0. < self.ct {
+            self.ct -= cycle.dur
+        } else {
+            self.ct = 0.
+        }
+        self.boost = (self.boost - cycle.dur).max(0.);
+
+        // 再装填は構えている砲のみ進める
+        let gt = self.gt;
+        let state = &mut self.states[gt.index()];
+        if let Some(rl) = state.reload.as_mut() {
+            *rl -= cycle.dur;
+            if *rl <= 0. {
+                state.reload = None;
+                state.ammo = gt.magazine().map(|m| m.size);
+            }
+        }
+
+        // 冷却は全ての砲で進める
+        for (state, gt) in self.states.iter_mut().zip(GunType::ALL) { if let Some(hp) = gt.heat() {
+            state.heat = (state.heat - hp.cool_rate * cycle.dur).max(0.);
+            if state.overheat && state.heat <= hp.recover {
+                state.overheat = false;
+            }
+        }}
+    }
+}
+
+/// 貫通の設定
+#[derive(Clone, Copy)]
+pub struct PenetrationParam {
+    /// 貫通できる敵の数
+    pub count: u32, 
+    /// 貫通ごとに残る速度の比率
+    pub vel_keep: f32, 
+    /// 貫通ごとに残るダメージの比率
+    pub damage_keep: f32, 
 }
 
 /// 砲タイプのギアの構造体
@@ -148,6 +412,49 @@ impl GearGun {
 pub struct GunGear {
     pub ty: GunGearType, 
     fuze_time: Option<f32>, 
+    /// 射撃時に追尾していた目標
+    target: Option<crate::game::enemy::enemy::EnemyRef>, 
+    /// 貫通済みの敵
+    penetrated: Vec<crate::game::enemy::enemy::EnemyIdent>, 
+    /// 貫通による減衰を含めたダメージの倍率
+    damage_r: f32, 
+}
+impl GunGear {
+    pub fn new(
+        ty: GunGearType, 
+        fuze_time: Option<f32>, 
+        target: Option<crate::game::enemy::enemy::EnemyRef>, 
+    ) -> Self { Self {
+        ty, 
+        fuze_time, 
+        target, 
+        penetrated: Vec::new(), 
+        damage_r: 1., 
+    }}
+
+    /// 射撃時に追尾していた目標
+    pub fn target(&self) -> Option<&crate::game::enemy::enemy::EnemyRef> {
+        self.target.as_ref()
+    }
+
+    /// 着弾・起爆時に発生させるコマンド
+    fn explode(&self, phys: &crate::game::ferris::ngear::GearPhys) -> Option<GComm> {
+        let base_vel: nalgebra::Vector2<f32> = [
+            phys.vel_a / 2. * phys.rotation.cos(), 
+            phys.vel_a / 2. * phys.rotation.sin(), 
+        ].into();
+        self.ty.area_damage()
+            .map(|param| GComm::AreaDamage { 
+                param, 
+                position: phys.position, 
+                base_vel, 
+            })
+            .or_else(|| self.ty.explode().map(|param| GComm::Explode { 
+                param, 
+                position: phys.position, 
+                base_vel, 
+            }))
+    }
 }
 impl super::GTypeTrait for GunGear {
     fn angle_diff(&self) -> Option<std::ops::Range<f32>> {
@@ -183,61 +490,89 @@ impl super::GTypeTrait for GunGear {
         >, 
         enemies: &mut crate::game::enemy::enemy::EnemyArray, 
         gcomm: &mut crate::game::ferris::ngear::gcomm::GCommQueue, 
+        _engage: &mut crate::game::enemy::engage::EngagementRegistry, 
+        feedback: &mut crate::game::feedback::Feedback, 
     ) -> bool {
         self.fuze_time.as_mut()
             .map(|ft| *ft -= cycle.dur);
 
-        let eref = enemies.enemies.iter_mut()
-            .map(|EntityRefMut { entity, .. }| entity)
-            .filter(|entity| aabb(*entity, &GPhysWrap {
-                gt: &super::GType::GunShot(self.clone()),
-                phys: phys,
-            }))
-            .map(|entity| {
-                let dist = entity.position - phys.position;
-                ((dist.x.powi(2) + dist.y.powi(2)).sqrt(), entity)
-            })
-            .fold(
-                None::<(f32, &mut Enemy)>, 
-                |
-                    init, 
-                    tg, 
-                | match init {
-                    None => Some(tg), 
-                    Some(e) if tg.0 < e.0 => Some(tg), 
-                    a @ Some(_) => a, 
-                }
-            )
-            .map(|(_, e)| e);
-        
-        let mut explode = || {
-            if let Some(exp) = self.ty.explode()
-                .map(|param| GComm::Explode { 
-                    param, 
-                    position: phys.position, 
-                    base_vel: [
-                        phys.vel_a / 2. * phys.rotation.cos(), 
-                        phys.vel_a / 2. * phys.rotation.sin(), 
-                    ].into() 
-                }
-            ) {
-                gcomm.push(exp)
+        // 直前の移動経路上で接触した敵を手前から順に処理する
+        let mut from = phys.prev_position;
+        while let Some((r, pos)) = enemies.first_swept_contact(&ShotBody {
+            ty: &self.ty, 
+            phys, 
+        }, from, &self.penetrated) {
+            let e = if let Some(e) = enemies.get_mut(&r) { e } else { break };
+            e.give_damage(self.ty.damage_type(), self.ty.damage() * self.damage_r, feedback);
+            gcomm.push(GComm::Particle {
+                emitter: crate::game::particle::Emitter::HitSpark, 
+                position: pos, 
+                rotation: phys.rotation + std::f32::consts::PI, 
+                base_vel: [0., 0.].into(), 
+            });
+            if let Some(st) = self.ty.status() { e.apply_status(st) }
+            match self.ty.penetration() {
+                // 貫通して減速・減衰しつつ飛び続ける
+                // 接触点から先の残りの移動は減速した分だけ進める
+                Some(pen) if (self.penetrated.len() as u32) < pen.count => {
+                    self.penetrated.push(e.ident.clone());
+                    self.damage_r *= pen.damage_keep;
+                    phys.vel_a *= pen.vel_keep;
+                    phys.position = pos + (phys.position - pos) * pen.vel_keep;
+                    from = pos;
+                }, 
+                // 止まる場合だけ接触点へ戻す
+                _ => {
+                    phys.position = pos;
+                    if let Some(exp) = self.explode(phys) { gcomm.push(exp) }
+                    return false
+                }, 
             }
-        };
-        if let Some(e) = eref {
-            e.give_damage(self.ty.damage());
-            explode();
-            false
+        }
+
+        if 0. < self.fuze_time.unwrap_or(1.) {
+            varea.in_visible(
+                phys.position, 
+                self.size()
+            )
         } else {
-            if 0. < self.fuze_time.unwrap_or(1.) {
-                varea.in_visible(
-                    phys.position, 
-                    self.size()
-                )
-            } else {
-                explode();
-                false
-            }
+            if let Some(exp) = self.explode(phys) { gcomm.push(exp) }
+            false
+        }
+    }
+}
+
+/// 当たり判定用の砲弾
+/// 貫通済みの敵の一覧を複製しないよう、形式と物理状態だけを参照する
+struct ShotBody<'a> {
+    ty: &'a GunGearType, 
+    phys: &'a GearPhys, 
+}
+impl PhysicBody for ShotBody<'_> {
+    fn position(&self) -> nalgebra::Point2<f32> {
+        self.phys.position
+    }
+
+    fn size(&self) -> nalgebra::Vector2<f32> {
+        self.ty.size()
+    }
+
+    fn rotation(&self) -> f32 {
+        self.phys.rotation
+    }
+
+    fn velocity(&self) -> nalgebra::Vector2<f32> {
+        [
+            self.phys.vel_a * self.phys.rotation.cos(), 
+            self.phys.vel_a * self.phys.rotation.sin()
+        ].into()
+    }
+}
+impl HasShape for ShotBody<'_> {
+    fn collider(&self) -> Collider {
+        Collider {
+            position: self.phys.position, 
+            shape: self.ty.shape(), 
         }
     }
 }
@@ -251,6 +586,8 @@ pub enum GunGearType {
     LargeGunBullet, 
     SmallRifleShell, 
     MiddleRifleShell, 
+    /// クラスター・ミサイルの子弾
+    ClusterBomblet, 
 }
 impl GunGearType {
     pub fn damage(&self) -> f32 { match self {
@@ -260,6 +597,7 @@ impl GunGearType {
         GunGearType::LargeGunBullet => 12.,
         GunGearType::SmallRifleShell => 48.,
         GunGearType::MiddleRifleShell => 128., 
+        GunGearType::ClusterBomblet => 6., 
     } }
 
     pub fn explode(&self) -> Option<
@@ -325,9 +663,99 @@ impl GunGearType {
             ltime_diff: Some(-1./7.5..1./7.5), 
             damage_r: 4.
         }), 
+        GunGearType::ClusterBomblet => Some(ExplodeParam { 
+            tex_rot: Some(
+                -240.0 * (std::f32::consts::PI / 180.)
+                .. 240.0 * (std::f32::consts::PI / 180.)
+            ), 
+            frag_count: 16, 
+            frag_diff: Some(-4..4), 
+            frvel_base: 420., 
+            frvel_diff: Some(-240.0..240.0), 
+            frsiz_base: 8., 
+            frsiz_diff: Some(-2.0..2.0), 
+            ltime_base: 1. / 10., 
+            ltime_diff: Some(-1./20.0..1./20.), 
+            damage_r: 1.5
+        }), 
         
         _ => None, 
     }}
+
+    /// 命中時のダメージの種類
+    pub fn damage_type(&self) -> DamageType { match self {
+        GunGearType::ClusterBomblet => DamageType::Explosive, 
+        _ => DamageType::Kinetic, 
+    }}
+
+    /// 命中時にかける状態異常
+    pub fn status(&self) -> Option<StatusEffect> { match self {
+        GunGearType::ShotPellet => Some(StatusEffect {
+            kind: StatusKind::Slow, 
+            magnitude: 0.8, 
+            duration: 1., 
+        }), 
+        GunGearType::MiddleRifleShell => Some(StatusEffect {
+            kind: StatusKind::Stun, 
+            magnitude: 0., 
+            duration: 0.4, 
+        }), 
+        GunGearType::ClusterBomblet => Some(StatusEffect {
+            kind: StatusKind::Burn, 
+            magnitude: 2., 
+            duration: 3., 
+        }), 
+        _ => None, 
+    }}
+
+    /// 貫通能力
+    pub fn penetration(&self) -> Option<PenetrationParam> { match self {
+        GunGearType::LargeGunBullet => Some(PenetrationParam {
+            count: 1, 
+            vel_keep: 0.7, 
+            damage_keep: 0.5, 
+        }), 
+        GunGearType::SmallRifleShell => Some(PenetrationParam {
+            count: 2, 
+            vel_keep: 0.8, 
+            damage_keep: 0.7, 
+        }), 
+        GunGearType::MiddleRifleShell => Some(PenetrationParam {
+            count: 4, 
+            vel_keep: 0.85, 
+            damage_keep: 0.75, 
+        }), 
+        _ => None, 
+    }}
+
+    /// 当たり判定の形状
+    pub fn shape(&self) -> Shape {
+        let size = self.size();
+        Shape::Circle { 
+            radius: size.x.max(size.y) * 0.5, 
+        }
+    }
+
+    /// 範囲ダメージ
+    /// 設定されている場合、破片はギアではなくパーティクルとして散らす
+    pub fn area_damage(&self) -> Option<AreaDamageParam> { match self {
+        GunGearType::LargeGunBullet => Some(AreaDamageParam {
+            radius: 48., 
+            damage: 16., 
+            edge_ratio: 0.25, 
+        }), 
+        GunGearType::SmallRifleShell => Some(AreaDamageParam {
+            radius: 96., 
+            damage: 64., 
+            edge_ratio: 0.25, 
+        }), 
+        GunGearType::MiddleRifleShell => Some(AreaDamageParam {
+            radius: 160., 
+            damage: 160., 
+            edge_ratio: 0.2, 
+        }), 
+        _ => None, 
+    }}
 }
 impl super::GTypeTrait for GunGearType {
     fn angle_diff(&self) -> Option<std::ops::Range<f32>> { match self {
@@ -349,6 +777,7 @@ impl super::GTypeTrait for GunGearType {
         GunGearType::MiddleRifleShell => Some(
             -std::f32::consts::PI * 0.0125 .. std::f32::consts::PI * 0.0125
         ), 
+        GunGearType::ClusterBomblet => None, 
     }}
 
     fn vel_default(&self) -> f32 { match self {
@@ -358,6 +787,7 @@ impl super::GTypeTrait for GunGearType {
         GunGearType::LargeGunBullet => 1800.,
         GunGearType::SmallRifleShell => 1650.,
         GunGearType::MiddleRifleShell => 1400., 
+        GunGearType::ClusterBomblet => 480., 
     }}
 
     fn vel_diff(&self) -> Option<std::ops::Range<f32>> { match self {
@@ -367,6 +797,7 @@ impl super::GTypeTrait for GunGearType {
         GunGearType::LargeGunBullet => Some(-80.0..80.0),
         GunGearType::SmallRifleShell => None,
         GunGearType::MiddleRifleShell => None, 
+        GunGearType::ClusterBomblet => Some(-160.0..160.0), 
     }}
 
     fn size(&self) -> nalgebra::Vector2<f32> { match self {
@@ -376,6 +807,7 @@ impl super::GTypeTrait for GunGearType {
         GunGearType::LargeGunBullet => [24., 24.].into(),
         GunGearType::SmallRifleShell => [32., 32.].into(),
         GunGearType::MiddleRifleShell => [48., 48.].into(), 
+        GunGearType::ClusterBomblet => [12., 12.].into(), 
     }}
 
     fn tex_rot_diff(&self) -> Option<std::ops::Range<f32>> { match self {
@@ -397,6 +829,9 @@ impl super::GTypeTrait for GunGearType {
         GunGearType::MiddleRifleShell => Some(
             -std::f32::consts::PI * 4. .. std::f32::consts::PI * 4.
         ),
+        GunGearType::ClusterBomblet => Some(
+            -std::f32::consts::PI * 4. .. std::f32::consts::PI * 4.
+        ),
     }}
 
     fn update(
@@ -412,5 +847,7 @@ impl super::GTypeTrait for GunGearType {
         >, 
         _enemies: &mut crate::game::enemy::enemy::EnemyArray, 
         _gcomm: &mut super::super::gcomm::GCommQueue, 
+        _engage: &mut crate::game::enemy::engage::EngagementRegistry, 
+        _feedback: &mut crate::game::feedback::Feedback, 
     ) -> bool { unreachable!() }
 }
\ No newline at end of file
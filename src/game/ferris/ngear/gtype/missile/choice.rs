@@ -3,45 +3,190 @@ use super::{
     *, 
     super::super::GPhysWrap, 
 };
+use crate::game::enemy::engage::EngagementRegistry;
 
-/// 単純な最も近い対象を選ぶ関数
-pub fn choice_simple_neerest(
+/// 目標の選択方式
+#[derive(Clone, Copy)]
+pub enum TargetStrategy {
+    /// 最も近い目標
+    Nearest, 
+    /// 画面下端を抜けるまでの時間が最も短い目標
+    MostUrgent, 
+    /// 予約済みのダメージを差し引いた残り体力が最も少ない目標
+    LowestHealth, 
+    /// 撃破時のスコアが最も高い目標
+    HighestScore, 
+    /// 交戦しているギアが最も少ない目標
+    LeastTargeted, 
+}
+impl TargetStrategy {
+    /// 目標の評価値
+    /// 小さいほど優先する
+    pub fn evaluate(
+        &self, 
+        phys: &GPhysWrap, 
+        enemy: &enemy::enemy::Enemy, 
+        varea: &simple2d::types::VisibleField, 
+        engage: &EngagementRegistry, 
+    ) -> f32 { match self {
+        TargetStrategy::Nearest => {
+            let dist = enemy.position - phys.position();
+            (dist.x.powi(2) + dist.y.powi(2)).sqrt()
+        }, 
+        TargetStrategy::MostUrgent => {
+            let bottom = varea.visible_area()[0].y;
+            let fall = (-enemy.velocity().y).max(1.);
+            (enemy.position.y - bottom) / fall
+        }, 
+        TargetStrategy::LowestHealth => engage.expected_health(enemy), 
+        TargetStrategy::HighestScore => -(enemy.enemy_type().score() as f32), 
+        TargetStrategy::LeastTargeted => engage.get(&enemy.ident).count as f32, 
+    }}
+
+    /// 候補を優先順に並べる
+    /// 候補はrangeの範囲内の敵から探し、
+    /// 予約済みのダメージで撃破が見込める目標は後回しにし、
+    /// 評価値が等しければ近いものを優先する
+    pub fn rank(
+        &self, 
+        phys: &GPhysWrap, 
+        enemies: &enemy::enemy::EnemyArray, 
+        varea: &simple2d::types::VisibleField, 
+        engage: &EngagementRegistry, 
+        range: f32, 
+        mut filter: impl FnMut(
+            &GPhysWrap, 
+            &enemy::enemy::Enemy
+        ) -> bool, 
+    ) -> Vec<EnemyRef> {
+        let mut candidates = enemies.query_radius(phys.position(), range)
+            .into_iter()
+            .filter_map(|r| enemies.get(&r).map(|e| (r, e)))
+            .filter(|(_, e)| {
+                filter(
+                    phys, 
+                    e, 
+                )
+            })
+            .map(|(enemyref, e)| {
+                let key = self.evaluate(phys, e, varea, engage);
+                let dist = TargetStrategy::Nearest.evaluate(
+                    phys, e, varea, engage
+                );
+                let doomed = engage.is_doomed(e);
+                (enemyref, doomed, key, dist)
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_by(|a, b| a.1.cmp(&b.1)
+            .then(a.2.total_cmp(&b.2))
+            .then(a.3.total_cmp(&b.3))
+        );
+        candidates.into_iter()
+            .map(|(e, _, _, _)| e)
+            .collect()
+    }
+
+    /// 最も優先する対象を選ぶ
+    pub fn choice(
+        &self, 
+        phys: &GPhysWrap, 
+        enemies: &enemy::enemy::EnemyArray, 
+        varea: &simple2d::types::VisibleField, 
+        engage: &EngagementRegistry, 
+        range: f32, 
+        filter: impl FnMut(
+            &GPhysWrap, 
+            &enemy::enemy::Enemy
+        ) -> bool, 
+    ) -> Option<EnemyRef> {
+        self.rank(phys, enemies, varea, engage, range, filter)
+            .into_iter()
+            .next()
+    }
+}
+
+/// 前方の扇形の範囲で目標を探索する関数
+/// FCSで指定された目標でなければ、扇形の範囲を大きく外れた時点で目標を解除する
+pub fn seek_cone(
+    target: &mut Option<enemy::enemy::EnemyRef>, 
+    fcs_controlled: bool, 
+    strategy: TargetStrategy, 
     phys: &GPhysWrap, 
     enemies: &enemy::enemy::EnemyArray, 
-    mut filter: impl FnMut(
-        &GPhysWrap,
+    varea: &simple2d::types::VisibleField, 
+    engage: &EngagementRegistry, 
+    range: f32, 
+    angle: f32, 
+) {
+    let fnc = |phys: &GPhysWrap, e: &enemy::enemy::Enemy| {
+        let dist = e.position - phys.position();
+
+        let a = f32::atan2(dist.y, dist.x);
+        // 相対角度を計算
+        let angle_diff = (
+            (a - phys.rotation()) + std::f32::consts::PI
+        ).rem_euclid(std::f32::consts::PI * 2.).abs()
+            - std::f32::consts::PI;
+
+        // 相対距離の計算
+        let dist = (dist.x.powi(2) + dist.y.powi(2)).sqrt();
+
+        dist.abs() < range && angle_diff.abs() < angle
+    };
+    match target {
+        e @ None => {
+            *e = strategy.choice(
+                phys, 
+                enemies, 
+                varea, 
+                engage, 
+                range, 
+                fnc, 
+            );
+        }, 
+        e @ Some(_) if !fcs_controlled => if let Some(
+            tgt
+        ) = enemies.get(
+            e.as_ref().unwrap()
+        ) {
+            let dist = tgt.position - phys.phys.position;
+            let a = f32::atan2(dist.y, dist.x);
+            let angle_diff = (
+                (a - phys.phys.rotation) + std::f32::consts::PI
+            ).rem_euclid(std::f32::consts::PI * 2.).abs()
+                - std::f32::consts::PI;
+            if !(angle_diff.abs() < angle * 2.) {
+                *e = None
+            }
+        }
+        _ => {}, 
+    }
+}
+
+/// 群れの中での順番に応じて対象を分散させて選ぶ関数
+/// 優先順に並べた候補のうち、slot番目(候補数で剰余)を選ぶ
+pub fn choice_spread(
+    strategy: TargetStrategy, 
+    phys: &GPhysWrap, 
+    enemies: &enemy::enemy::EnemyArray, 
+    varea: &simple2d::types::VisibleField, 
+    engage: &EngagementRegistry, 
+    range: f32, 
+    filter: impl FnMut(
+        &GPhysWrap, 
         &enemy::enemy::Enemy
     ) -> bool, 
+    slot: u32, 
 ) -> Option<enemy::enemy::EnemyRef> {
-    enemies.enemies.iter()
-        .filter(|e| {
-            filter(
-                phys, 
-                e.entity, 
-            )
-        })
-        .map(|e| {
-            let dist = e.entity.position - phys.position();
-            let dist = dist.x.powi(2) + dist.y.powi(2);
-            let enemyref = EnemyRef {
-                ident: e.entity.ident.clone(), 
-                idx: e.idx, 
-            };
-            (enemyref, dist.sqrt().abs())
-        })
-        .fold(
-            None::<(EnemyRef, f32)>, 
-            |
-                init, 
-                (
-                    enemyref, 
-                    dist
-                )
-            | match init {
-                None => Some((enemyref, dist)), 
-                Some((_, ed)) if dist <= ed => Some((enemyref, dist)), 
-                Some(_) => init, 
-            }
-        )
-        .map(|e| e.0)
-}
\ No newline at end of file
+    let mut candidates = strategy.rank(
+        phys, 
+        enemies, 
+        varea, 
+        engage, 
+        range, 
+        filter, 
+    );
+    if candidates.is_empty() { return None }
+    let idx = slot as usize % candidates.len();
+    Some(candidates.swap_remove(idx))
+}
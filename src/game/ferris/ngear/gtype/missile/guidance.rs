@@ -0,0 +1,230 @@
+//! ミサイルの誘導則の実装
+
+use tm_wg_wrapper::prelude::nalgebra::{Point2, Vector2};
+
+use super::MissileHomingMode;
+
+/// 角度を-π..πの範囲に正規化する
+pub fn wrap_angle(angle: f32) -> f32 {
+    (angle + std::f32::consts::PI).rem_euclid(std::f32::consts::PI * 2.)
+        - std::f32::consts::PI
+}
+
+/// 旋回限界の範囲内で目標の角度へ向ける
+/// 差が旋回限界以内であれば目標の角度に合わせる
+pub fn turn_toward(
+    rotation: f32, 
+    desired: f32, 
+    max_turn: f32, 
+) -> f32 {
+    let diff = wrap_angle(desired - rotation);
+    if diff.abs() <= max_turn {
+        rotation + diff
+    } else {
+        rotation + max_turn * diff.signum()
+    }
+}
+
+/// 視線角速度の計算
+/// rel_posは目標への相対位置、rel_velは目標の相対速度
+pub fn los_rate(
+    rel_pos: Vector2<f32>, 
+    rel_vel: Vector2<f32>, 
+) -> f32 {
+    let r2 = rel_pos.x.powi(2) + rel_pos.y.powi(2);
+    if r2 <= f32::EPSILON {
+        0.
+    } else {
+        (rel_pos.x * rel_vel.y - rel_pos.y * rel_vel.x) / r2
+    }
+}
+
+/// 会合点の計算
+/// 目標の等速直線運動を仮定し、会合できなければ目標の現在位置を返す
+pub fn lead_point(
+    own_pos: Point2<f32>, 
+    own_speed: f32, 
+    tgt_pos: Point2<f32>, 
+    tgt_vel: Vector2<f32>, 
+) -> Point2<f32> {
+    let r = tgt_pos - own_pos;
+    let a = tgt_vel.x.powi(2) + tgt_vel.y.powi(2) - own_speed.powi(2);
+    let b = 2. * (r.x * tgt_vel.x + r.y * tgt_vel.y);
+    let c = r.x.powi(2) + r.y.powi(2);
+    let t = if a.abs() < 1e-6 {
+        if b < 0. { Some(-c / b) } else { None }
+    } else {
+        let disc = b.powi(2) - 4. * a * c;
+        if disc < 0. {
+            None
+        } else {
+            let sq = disc.sqrt();
+            [(-b - sq) / (2. * a), (-b + sq) / (2. * a)].into_iter()
+                .filter(|t| 0. < *t)
+                .fold(None, |init: Option<f32>, t| match init {
+                    Some(i) if i <= t => Some(i), 
+                    _ => Some(t), 
+                })
+        }
+    };
+    t.map_or(tgt_pos, |t| tgt_pos + tgt_vel * t)
+}
+
+/// 誘導則に従って旋回後の角度を求める
+/// max_turn_rateはラジアン毎秒
+pub fn steer(
+    mode: MissileHomingMode, 
+    nav_const: f32, 
+    max_turn_rate: f32, 
+    own_pos: Point2<f32>, 
+    own_vel: Vector2<f32>, 
+    rotation: f32, 
+    tgt_pos: Point2<f32>, 
+    tgt_vel: Vector2<f32>, 
+    dt: f32, 
+) -> f32 {
+    let max_turn = max_turn_rate * dt;
+    let r = tgt_pos - own_pos;
+    let los = f32::atan2(r.y, r.x);
+    match mode {
+        MissileHomingMode::PureNavigate => turn_toward(
+            rotation, los, max_turn
+        ), 
+        MissileHomingMode::LeadNavigate => {
+            let speed = (own_vel.x.powi(2) + own_vel.y.powi(2)).sqrt();
+            let lp = lead_point(own_pos, speed, tgt_pos, tgt_vel) - own_pos;
+            turn_toward(rotation, f32::atan2(lp.y, lp.x), max_turn)
+        }, 
+        MissileHomingMode::ProportionalNavigate => {
+            let rel_vel = tgt_vel - own_vel;
+            let dist = (r.x.powi(2) + r.y.powi(2)).sqrt().max(f32::EPSILON);
+            let closing = -(r.x * rel_vel.x + r.y * rel_vel.y) / dist;
+
+            // 離れていく、もしくは背後にいる目標は比例誘導で捉えられないため単純誘導で向き直る
+            if closing <= 0. || std::f32::consts::PI * 0.5 < wrap_angle(los - rotation).abs() {
+                turn_toward(rotation, los, max_turn)
+            } else {
+                let omega = (nav_const * los_rate(r, rel_vel))
+                    .clamp(-max_turn_rate, max_turn_rate);
+                rotation + omega * dt
+            }
+        }, 
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 1. / 60.;
+
+    /// ミサイルを飛ばし、目標との最接近距離を返す
+    fn simulate(
+        mode: MissileHomingMode, 
+        target: impl Fn(f32) -> (Point2<f32>, Vector2<f32>), 
+    ) -> f32 {
+        let mut pos = Point2::new(0., 0.);
+        let mut rotation = std::f32::consts::PI * 0.5;
+        let mut speed = 120.;
+        let mut nearest = f32::MAX;
+        let mut t = 0.;
+        while t < 3. {
+            let (tp, tv) = target(t);
+            let vel = Vector2::new(speed * rotation.cos(), speed * rotation.sin());
+            rotation = steer(
+                mode, 
+                4., 
+                360f32.to_radians(), 
+                pos, 
+                vel, 
+                rotation, 
+                tp, 
+                tv, 
+                DT, 
+            );
+            speed = (speed + 620. * DT).min(1280.);
+            pos += Vector2::new(speed * rotation.cos(), speed * rotation.sin()) * DT;
+            t += DT;
+
+            let d = target(t).0 - pos;
+            nearest = nearest.min((d.x.powi(2) + d.y.powi(2)).sqrt());
+            if nearest < 20. { break }
+        }
+        nearest
+    }
+
+    fn straight(t: f32) -> (Point2<f32>, Vector2<f32>) {
+        (Point2::new(-300. + 200. * t, 600.), Vector2::new(200., 0.))
+    }
+
+    fn weaving(t: f32) -> (Point2<f32>, Vector2<f32>) {
+        (
+            Point2::new(-200. + 250. / 3. * (3. * t).sin(), 700. - 100. * t), 
+            Vector2::new(250. * (3. * t).cos(), -100.), 
+        )
+    }
+
+    #[test]
+    fn wrap_angle_range() {
+        assert!((wrap_angle(std::f32::consts::PI * 1.5) + std::f32::consts::PI * 0.5).abs() < 1e-5);
+        assert!((wrap_angle(-std::f32::consts::PI * 1.5) - std::f32::consts::PI * 0.5).abs() < 1e-5);
+        assert!((wrap_angle(0.25) - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn turn_toward_small_error_snaps() {
+        // 旋回限界内の小さな正の誤差では逆方向に回らない
+        assert!((turn_toward(0., 0.01, 0.1) - 0.01).abs() < 1e-6);
+        assert!((turn_toward(0., -0.01, 0.1) + 0.01).abs() < 1e-6);
+    }
+
+    #[test]
+    fn turn_toward_is_rate_limited() {
+        assert!((turn_toward(0., 1., 0.1) - 0.1).abs() < 1e-6);
+        assert!((turn_toward(0., -1., 0.1) + 0.1).abs() < 1e-6);
+        // -π/π をまたぐ場合は近い側へ回る
+        assert!(turn_toward(3., -3., 0.1) > 3.);
+    }
+
+    #[test]
+    fn los_rate_sign() {
+        let rate = los_rate(Vector2::new(0., 100.), Vector2::new(10., 0.));
+        assert!((rate + 0.1).abs() < 1e-6);
+        assert_eq!(los_rate(Vector2::new(0., 0.), Vector2::new(10., 0.)), 0.);
+    }
+
+    #[test]
+    fn lead_point_of_crossing_target() {
+        let lp = lead_point(
+            Point2::new(0., 0.), 
+            500., 
+            Point2::new(-300., 400.), 
+            Vector2::new(300., 0.), 
+        );
+        // 会合点までの所要時間は目標と自機で一致する
+        let t_tgt = (lp.x + 300.) / 300.;
+        let t_own = (lp.x.powi(2) + lp.y.powi(2)).sqrt() / 500.;
+        assert!((t_tgt - t_own).abs() < 1e-3);
+    }
+
+    #[test]
+    fn intercept_straight_target() {
+        for mode in [
+            MissileHomingMode::PureNavigate, 
+            MissileHomingMode::LeadNavigate, 
+            MissileHomingMode::ProportionalNavigate, 
+        ] {
+            assert!(simulate(mode, straight) < 20.);
+        }
+    }
+
+    #[test]
+    fn intercept_weaving_target() {
+        for mode in [
+            MissileHomingMode::LeadNavigate, 
+            MissileHomingMode::ProportionalNavigate, 
+        ] {
+            assert!(simulate(mode, weaving) < 20.);
+        }
+    }
+}
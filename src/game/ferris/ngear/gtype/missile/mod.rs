@@ -2,54 +2,243 @@
 
 use tm_wg_wrapper::{
     prelude::*, 
-    util::simple2d::{physic::{PhysicBody, self}, entity_holder::EntityRefMut}, 
+    util::simple2d::physic::PhysicBody, 
 };
 
-use crate::game::{physic::aabb, ferris::ngear::gcomm::{explode::ExplodeParam, GComm}};
-use crate::game::enemy::{self, enemy::EnemyRef};
+use crate::game::{ferris::ngear::gcomm::{explode::ExplodeParam, cluster::ClusterParam, area::AreaDamageParam, GComm}};
+use crate::game::enemy::{self, enemy::EnemyRef, damage::DamageType, status::{StatusEffect, StatusKind}};
 
 use super::GTypeTrait;
+use super::gun::{GTToggle, GunGearType};
 
 pub mod choice;
+pub mod guidance;
 
 /// 発射するミサイルの形式
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum LaunchMissileType {
     LightMissile, 
+    HeavyMissile, 
+    ClusterMissile, 
+    SwarmSalvo, 
+    SwarmMissile, 
 }
+impl LaunchMissileType {
+    pub fn toggle(
+        &mut self, 
+        gt_toggle: GTToggle, 
+    ) { *self = match gt_toggle {
+        GTToggle::Forward => match self {
+            LaunchMissileType::LightMissile => LaunchMissileType::HeavyMissile,
+            LaunchMissileType::HeavyMissile => LaunchMissileType::ClusterMissile,
+            LaunchMissileType::ClusterMissile => LaunchMissileType::SwarmSalvo,
+            LaunchMissileType::SwarmSalvo => LaunchMissileType::SwarmMissile,
+            LaunchMissileType::SwarmMissile => LaunchMissileType::LightMissile,
+        },
+        GTToggle::Backward => match self {
+            LaunchMissileType::LightMissile => LaunchMissileType::SwarmMissile,
+            LaunchMissileType::HeavyMissile => LaunchMissileType::LightMissile,
+            LaunchMissileType::ClusterMissile => LaunchMissileType::HeavyMissile,
+            LaunchMissileType::SwarmSalvo => LaunchMissileType::ClusterMissile,
+            LaunchMissileType::SwarmMissile => LaunchMissileType::SwarmSalvo,
+        },
+    }}
+
+    /// 表示用の名前
+    pub fn name(&self) -> &'static str { match self {
+        LaunchMissileType::LightMissile => "LIGHT MISSILE",
+        LaunchMissileType::HeavyMissile => "HEAVY MISSILE",
+        LaunchMissileType::ClusterMissile => "CLUSTER MISSILE",
+        LaunchMissileType::SwarmSalvo => "SWARM SALVO",
+        LaunchMissileType::SwarmMissile => "SWARM MISSILE",
+    }}
+
+    pub fn cool_time(&self) -> f32 { match self {
+        LaunchMissileType::LightMissile => 0.25,
+        LaunchMissileType::HeavyMissile => 1.,
+        LaunchMissileType::ClusterMissile => 1.2,
+        LaunchMissileType::SwarmSalvo => 1.5,
+        LaunchMissileType::SwarmMissile => 1.5,
+    }}
+
+    /// 一度に発射するミサイルの数
+    pub fn launch_count(&self) -> u32 { match self {
+        LaunchMissileType::LightMissile => 1,
+        LaunchMissileType::HeavyMissile => 1,
+        LaunchMissileType::ClusterMissile => 1,
+        LaunchMissileType::SwarmSalvo => 6,
+        LaunchMissileType::SwarmMissile => 8,
+    }}
+
+    /// 一度の発射で消費する弾数
+    pub fn ammo_cost(&self) -> u32 { match self {
+        LaunchMissileType::LightMissile => 1,
+        LaunchMissileType::HeavyMissile => 3,
+        LaunchMissileType::ClusterMissile => 4,
+        LaunchMissileType::SwarmSalvo => 6,
+        LaunchMissileType::SwarmMissile => 4,
+    }}
+
+    /// 斉射時の発射角度の広がり
+    pub fn launch_spread(&self) -> f32 { match self {
+        LaunchMissileType::SwarmSalvo => std::f32::consts::PI / 3.,
+        LaunchMissileType::SwarmMissile => std::f32::consts::PI / 2.,
+        _ => 0., 
+    }}
+
+    /// ロックオン射撃で発射するミサイルの形式
+    /// 斉射形式のものは軽量ミサイルに置き換える
+    pub fn lock_missile(&self) -> LaunchMissileType { match self {
+        LaunchMissileType::SwarmSalvo 
+        | LaunchMissileType::SwarmMissile => LaunchMissileType::LightMissile,
+        lt => *lt, 
+    }}
+
+    /// 発射するミサイルの生成
+    /// slotは斉射の中での順番
+    pub fn missile(
+        &self, 
+        target: Option<EnemyRef>, 
+        slot: u32, 
+    ) -> MissileGearType { match self {
+        LaunchMissileType::LightMissile => MissileGearType::LightMissile(
+            LightMissile { 
+                fcs_controlled: target.is_some(), 
+                target, 
+                strategy: choice::TargetStrategy::LeastTargeted, 
+            }
+        ),
+        // 斉射では撃破の近い目標から仕留める
+        LaunchMissileType::SwarmSalvo => MissileGearType::LightMissile(
+            LightMissile { 
+                fcs_controlled: target.is_some(), 
+                target, 
+                strategy: choice::TargetStrategy::LowestHealth, 
+            }
+        ),
+        LaunchMissileType::HeavyMissile => MissileGearType::HeavyMissile(
+            HeavyMissile { fcs_controlled: target.is_some(), target }
+        ),
+        LaunchMissileType::ClusterMissile => MissileGearType::ClusterMissile(
+            ClusterMissile { fcs_controlled: target.is_some(), target }
+        ),
+        LaunchMissileType::SwarmMissile => MissileGearType::SwarmMissile(
+            SwarmMissile { target: None, slot }
+        ),
+    }}
+}
+
+/// ミサイルの最大携行数
+pub const MISSILE_AMMO_MAX: u32 = 96;
 
 /// ミサイル発射機
 pub struct MissileLauncher {
+    pub lt: LaunchMissileType, 
     ct: f32, 
+    ammo: u32, 
 }
 impl Default for MissileLauncher {
     fn default() -> Self {
-        Self { ct: 0. }
+        Self { lt: LaunchMissileType::LightMissile, ct: 0., ammo: 48 }
     }
 }
 impl MissileLauncher {
 
+    /// ミサイルの補充
+    pub fn add_ammo(&mut self, count: u32) {
+        self.ammo = (self.ammo + count).min(MISSILE_AMMO_MAX);
+    }
+
+    /// ミサイルの残数
+    pub fn ammo(&self) -> u32 {
+        self.ammo
+    }
+
+    /// 現在のクールタイム
+    pub fn ct(&self) -> f32 {
+        self.ct
+    }
+
+    /// 発射するミサイルの切り替え
+    pub fn toggle(&mut self, gt_toggle: GTToggle) {
+        self.lt.toggle(gt_toggle)
+    }
+
     /// 射撃処理
     pub fn shoot(
         &mut self, 
         ferris: &crate::game::ferris::ferris::FerrisBody, 
         aim: Option<&crate::game::ferris::aim::Aim>, 
         gears: &mut super::super::array::GearInstances, 
-    ) { if self.ct == 0.0 {
+    ) { if self.ct == 0.0 && self.lt.ammo_cost() <= self.ammo {
         let target = aim.map(|s| match &s.state {
             crate::game::ferris::aim::AimState::Tracking { 
                 enemy, .. 
             } => Some(enemy.clone()),
             _ => None, 
         }).flatten();
-        let lm = MissileGearType::LightMissile(
-            LightMissile { fcs_controlled: target.is_some(), target }
-        );
+        let count = self.lt.launch_count();
+        let spread = self.lt.launch_spread();
+        for i in 0..count {
+            let offset = if 1 < count {
+                spread * (i as f32 / (count - 1) as f32 - 0.5)
+            } else { 0. };
+            Self::launch(
+                ferris, 
+                self.lt.missile(target.clone(), i), 
+                offset, 
+                gears, 
+            );
+        }
+
+        self.ammo -= self.lt.ammo_cost();
+        self.ct += self.lt.cool_time();
+    }}
+
+    /// ロックオンした目標への一斉射撃
+    /// ロックオン一つにつき一発のミサイルを、ロックオンした順に撃てる分だけ発射します
+    /// 発射した数を返し、冷却中であれば何も発射せず0を返します
+    pub fn shoot_locks(
+        &mut self, 
+        ferris: &crate::game::ferris::ferris::FerrisBody, 
+        locks: &[crate::game::ferris::aim::LockOn], 
+        gears: &mut super::super::array::GearInstances, 
+    ) -> usize { if self.ct == 0.0 && !locks.is_empty() {
+        let lt = self.lt.lock_missile();
+        let count = locks.len()
+            .min((self.ammo / lt.ammo_cost().max(1)) as usize);
+        if count == 0 { return 0 }
+        for (i, lock) in locks.iter().take(count).enumerate() {
+            let offset = if 1 < count {
+                std::f32::consts::PI / 3. * (i as f32 / (count - 1) as f32 - 0.5)
+            } else { 0. };
+            Self::launch(
+                ferris, 
+                lt.missile(Some(lock.enemy.clone()), i as u32), 
+                offset, 
+                gears, 
+            );
+            self.ammo -= lt.ammo_cost();
+        }
+        self.ct += lt.cool_time();
+        count
+    } else {
+        0
+    }}
+
+    /// ミサイル一発の発射
+    fn launch(
+        ferris: &crate::game::ferris::ferris::FerrisBody, 
+        lm: MissileGearType, 
+        offset: f32, 
+        gears: &mut super::super::array::GearInstances, 
+    ) {
         let gb = crate::RNG.with(|r| {
             let mut rng = r.borrow_mut();
             let gp = lm.vel_calc(
                 &mut *rng, 
                 ferris.position, 
-                ferris.rotation + std::f32::consts::PI * 0.5, 
+                ferris.rotation + std::f32::consts::PI * 0.5 + offset, 
                 ferris.velocity
             );
             let tr = lm.calc_tex_rot(&mut *rng);
@@ -61,9 +250,7 @@ impl MissileLauncher {
             }
         });
         gears.push_gb(gb);
-
-        self.ct += 0.25;
-    }}
+    }
 
     /// 更新処理
     /// クールタイムの計算をします
@@ -78,48 +265,76 @@ impl MissileLauncher {
 }
 
 /// ミサイルの誘導方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum MissileHomingMode {
     /// 単純誘導
     /// ターゲット座標を単純に追尾する
     PureNavigate, 
+    /// 見越し誘導
+    /// 等速直線運動を仮定した会合点へ向かう
+    LeadNavigate, 
     /// 比例誘導
-    /// 移動ターゲットとの相対速度から追尾する
+    /// 視線角速度に航法定数を掛けた角速度で旋回する
     ProportionalNavigate, 
 }
 impl MissileHomingMode {
-    pub fn calc_homing_v(
+    /// 誘導後の角度を求める
+    pub fn steer(
         &self, 
+        nav_const: f32, 
+        max_turn_rate: f32, 
         own: &impl PhysicBody, 
         target: &impl PhysicBody, 
-    ) -> nalgebra::Vector2<f32> { match self {
-        MissileHomingMode::PureNavigate => {
-            target.position() - own.position()
-        },
-        MissileHomingMode::ProportionalNavigate => {
-            physic::deviation_pos(
-                own, 
-                target, 
-                {
-                    let ovel = own.velocity();
-                    (ovel.x.powi(2) + ovel.y.powi(2)).sqrt()
-                }
-            ) - own.position()
-        },
-    }}
+        dt: f32, 
+    ) -> f32 {
+        guidance::steer(
+            *self, 
+            nav_const, 
+            max_turn_rate, 
+            own.position(), 
+            own.velocity(), 
+            own.rotation(), 
+            target.position(), 
+            target.velocity(), 
+            dt, 
+        )
+    }
 }
 
 /// ミサイルギアの形式
 #[derive(Clone)]
 pub enum MissileGearType {
     LightMissile(LightMissile), 
+    HeavyMissile(HeavyMissile), 
+    ClusterMissile(ClusterMissile), 
+    SwarmMissile(SwarmMissile), 
 }
 impl MissileGearType {
     pub fn damage(&self) -> f32 { match self {
         MissileGearType::LightMissile(_) => 20.,
+        MissileGearType::HeavyMissile(_) => 160.,
+        MissileGearType::ClusterMissile(_) => 10.,
+        MissileGearType::SwarmMissile(_) => 8.,
     } }
 
-    pub fn mode(&self) -> MissileHomingMode { match self {
-        MissileGearType::LightMissile(_) => MissileHomingMode::ProportionalNavigate,
+    pub fn mode(&self) -> MissileHomingMode { 
+        self.missile().mode()
+    }
+
+    /// ミサイルとしての実装への参照
+    pub fn missile(&self) -> &dyn MissileTrait { match self {
+        MissileGearType::LightMissile(lm) => lm,
+        MissileGearType::HeavyMissile(hm) => hm,
+        MissileGearType::ClusterMissile(cm) => cm,
+        MissileGearType::SwarmMissile(sm) => sm,
+    }}
+
+    /// ミサイルとしての実装への可変参照
+    pub fn missile_mut(&mut self) -> &mut dyn MissileTrait { match self {
+        MissileGearType::LightMissile(lm) => lm,
+        MissileGearType::HeavyMissile(hm) => hm,
+        MissileGearType::ClusterMissile(cm) => cm,
+        MissileGearType::SwarmMissile(sm) => sm,
     }}
 
     pub fn explode(&self) -> Option<ExplodeParam> { match self {
@@ -138,6 +353,80 @@ impl MissileGearType {
             ltime_diff: Some(-1./7.5..1./7.5), 
             damage_r: 2.
         }),
+        MissileGearType::HeavyMissile(_) => Some(ExplodeParam { 
+            tex_rot: Some(
+                -360.0 * (std::f32::consts::PI / 180.)
+                .. 360.0 * (std::f32::consts::PI / 180.)
+            ), 
+            frag_count: 320, 
+            frag_diff: Some(-64..64), 
+            frvel_base: 560., 
+            frvel_diff: Some(-480.0..480.0), 
+            frsiz_base: 14., 
+            frsiz_diff: Some(-6.0..6.0), 
+            ltime_base: 1. / 2.5, 
+            ltime_diff: Some(-1./7.5..1./7.5), 
+            damage_r: 5.
+        }),
+        MissileGearType::ClusterMissile(_) => None,
+        MissileGearType::SwarmMissile(_) => Some(ExplodeParam { 
+            tex_rot: Some(
+                -360.0 * (std::f32::consts::PI / 180.)
+                .. 360.0 * (std::f32::consts::PI / 180.)
+            ), 
+            frag_count: 32, 
+            frag_diff: Some(-8..8), 
+            frvel_base: 360., 
+            frvel_diff: Some(-240.0..240.0), 
+            frsiz_base: 8., 
+            frsiz_diff: Some(-2.0..2.0), 
+            ltime_base: 1. / 8., 
+            ltime_diff: Some(-1./20.0..1./20.), 
+            damage_r: 1.5
+        }),
+    }}
+
+    /// 命中時にかける状態異常
+    pub fn status(&self) -> Option<StatusEffect> { match self {
+        MissileGearType::LightMissile(_) => Some(StatusEffect {
+            kind: StatusKind::Mark, 
+            magnitude: 1.25, 
+            duration: 3., 
+        }), 
+        MissileGearType::HeavyMissile(_) => Some(StatusEffect {
+            kind: StatusKind::Stun, 
+            magnitude: 0., 
+            duration: 0.6, 
+        }), 
+        _ => None, 
+    }}
+
+    /// 範囲ダメージ
+    /// 設定されている場合、破片はギアではなくパーティクルとして散らす
+    pub fn area_damage(&self) -> Option<AreaDamageParam> { match self {
+        MissileGearType::HeavyMissile(_) => Some(AreaDamageParam {
+            radius: 192., 
+            damage: 240., 
+            edge_ratio: 0.2, 
+        }), 
+        _ => None, 
+    }}
+
+    /// 子弾の散布
+    pub fn cluster(&self) -> Option<ClusterParam> { match self {
+        MissileGearType::ClusterMissile(_) => Some(ClusterParam { 
+            count: 12, 
+            spread: std::f32::consts::PI * 0.2, 
+            fuze_base: 0.3, 
+            fuze_diff: Some(-0.1..0.1), 
+            bomblet: GunGearType::ClusterBomblet, 
+        }),
+        _ => None, 
+    }}
+
+    /// 子弾を散布する目標との距離
+    pub fn cluster_release_dist(&self) -> f32 { match self {
+        _ => 160., 
     }}
 }
 impl super::super::GTypeTrait for MissileGearType {
@@ -145,17 +434,23 @@ impl super::super::GTypeTrait for MissileGearType {
         None
     }
 
-    fn vel_default(&self) -> f32 {
-        120.
-    }
+    fn vel_default(&self) -> f32 { match self {
+        MissileGearType::LightMissile(_) => 120.,
+        MissileGearType::HeavyMissile(_) => 60.,
+        MissileGearType::ClusterMissile(_) => 100.,
+        MissileGearType::SwarmMissile(_) => 600.,
+    }}
 
     fn vel_diff(&self) -> Option<std::ops::Range<f32>> {
         None
     }
 
-    fn size(&self) -> nalgebra::Vector2<f32> {
-        [20., 20.].into()
-    }
+    fn size(&self) -> nalgebra::Vector2<f32> { match self {
+        MissileGearType::LightMissile(_) => [20., 20.].into(),
+        MissileGearType::HeavyMissile(_) => [32., 32.].into(),
+        MissileGearType::ClusterMissile(_) => [26., 26.].into(),
+        MissileGearType::SwarmMissile(_) => [24., 24.].into(),
+    }}
 
     fn tex_rot_diff(&self) -> Option<std::ops::Range<f32>> {
         Some(-std::f32::consts::PI * 2.0 .. std::f32::consts::PI * 2.0)
@@ -173,95 +468,139 @@ impl super::super::GTypeTrait for MissileGearType {
         >, 
         enemies: &mut enemy::enemy::EnemyArray, 
         gcomm: &mut super::super::gcomm::GCommQueue, 
+        engage: &mut crate::game::enemy::engage::EngagementRegistry, 
+        feedback: &mut crate::game::feedback::Feedback, 
     ) -> bool { 
         let s = self.clone();
-        match self {
-            Self::LightMissile(lm) => {
-                lm.remove_none_target(enemies);
-                if let Some(ferris) = ferris {
-                    lm.seek_target(
-                        super::super::GPhysWrap {
-                            gt: &super::GType::Missile(s.clone()),
-                            phys,
-                        }, 
-                        ferris, 
-                        enemies
-                    );
-                }
+        let damage = self.damage();
+        let m = self.missile_mut();
+        m.remove_none_target(enemies);
+        if let Some(ferris) = ferris {
+            let acquired = m.target().is_none();
+            m.seek_target(
+                super::super::GPhysWrap {
+                    gt: &super::GType::Missile(s.clone()),
+                    phys,
+                }, 
+                ferris, 
+                enemies, 
+                varea, 
+                engage, 
+            );
+            // 新たに捉えた目標は同じティックの後続のギアから見えるよう即座に予約する
+            if acquired { if let Some(e) = m.target().and_then(|t| enemies.get(t)) {
+                engage.reserve(e, DamageType::Explosive, damage)
+            }}
+        }
 
-                // 追尾機能
-                lm.homing(
-                    cycle, 
-                    enemies, 
-                    super::super::GPhysWrapMut {
-                        gt: &super::GType::Missile(s.clone()), 
-                        phys
-                    }
-                );
+        // 追尾機能
+        m.homing(
+            cycle, 
+            enemies, 
+            super::super::GPhysWrapMut {
+                gt: &super::GType::Missile(s.clone()), 
+                phys
+            }
+        );
 
-                // 速度の更新
-                let (vup, vmax) = {
-                    let (vup, vmax) = match self {
-                        MissileGearType::LightMissile(lm) => lm.speed_boost_max(),
-                    };
-                    (vup * cycle.dur, vmax)
-                };
-                
-                // 加速
-                if phys.vel_a < vmax {
-                    let tmp = phys.vel_a + vup;
-                    if tmp < vmax {
-                        phys.vel_a = tmp
-                    } else {
-                        phys.vel_a = vmax
-                    }
-                }
+        // 速度の更新
+        let (vup, vmax) = {
+            let (vup, vmax) = m.speed_boost_max();
+            (vup * cycle.dur, vmax)
+        };
+        
+        // 加速
+        if phys.vel_a < vmax {
+            let tmp = phys.vel_a + vup;
+            if tmp < vmax {
+                phys.vel_a = tmp
+            } else {
+                phys.vel_a = vmax
+            }
+        }
 
-                let eref = enemies.enemies.iter_mut()
-                    .map(|EntityRefMut { entity, .. }| entity)
-                    .filter(|entity| aabb(*entity, &super::super::GPhysWrap {
-                        gt: &super::GType::Missile(s.clone()),
-                        phys: phys,
-                    }))
-                    .map(|entity| {
-                        let dist = entity.position - phys.position;
-                        ((dist.x.powi(2) + dist.y.powi(2)).sqrt(), entity)
-                    })
-                    .fold(
-                        None::<(f32, &mut enemy::enemy::Enemy)>, 
-                        |
-                            init, 
-                            tg, 
-                        | match init {
-                            None => Some(tg), 
-                            Some(e) if tg.0 < e.0 => Some(tg), 
-                            a @ Some(_) => a, 
-                        }
-                    )
-                    .map(|(_, e)| e);
-            
-                if let Some(e) = eref {
-                    e.give_damage(self.damage());
-                    if let Some(exp) = self.explode()
-                        .map(|param| GComm::Explode { 
-                            param, 
-                            position: phys.position, 
-                            base_vel: [
-                                phys.vel_a / 2. * phys.rotation.cos(), 
-                                phys.vel_a / 2. * phys.rotation.sin(), 
-                            ].into() 
-                        }
-                    ) {
-                        gcomm.push(exp)
-                    }
-                    false
-                } else {
-                    varea.in_visible(
-                        phys.position, 
-                        self.size()
-                    )
-                }
-            },
+        // 排気の演出
+        gcomm.push(GComm::Particle {
+            emitter: crate::game::particle::Emitter::Exhaust, 
+            position: phys.position - nalgebra::Vector2::new(
+                phys.rotation.cos() * self.size().y * 0.5, 
+                phys.rotation.sin() * self.size().y * 0.5, 
+            ), 
+            rotation: phys.rotation + std::f32::consts::PI, 
+            base_vel: [0., 0.].into(), 
+        });
+
+        // 子弾の散布
+        let cluster = self.cluster()
+            .map(|param| GComm::Cluster { 
+                param, 
+                position: phys.position, 
+                rotation: phys.rotation, 
+                base_vel: [
+                    phys.vel_a / 2. * phys.rotation.cos(), 
+                    phys.vel_a / 2. * phys.rotation.sin(), 
+                ].into() 
+            });
+        if cluster.is_some() { if let Some(tgt) = self.missile().target()
+            .map(|t| enemies.get(t))
+            .flatten()
+        {
+            let dist = tgt.position - phys.position;
+            if (dist.x.powi(2) + dist.y.powi(2)).sqrt() < self.cluster_release_dist() {
+                gcomm.push(cluster.unwrap());
+                return false
+            }
+        }}
+
+        // 直前の移動経路上で最初に接触した敵
+        let hit = enemies.first_swept_contact(&super::super::GPhysWrap {
+            gt: &super::GType::Missile(s.clone()),
+            phys: phys,
+        }, phys.prev_position, &[]);
+        let eref = match hit {
+            Some((r, pos)) => {
+                phys.position = pos;
+                enemies.get_mut(&r)
+            }, 
+            None => None, 
+        };
+    
+        if let Some(e) = eref {
+            e.give_damage(DamageType::Explosive, self.damage(), feedback);
+            if let Some(st) = self.status() { e.apply_status(st) }
+            gcomm.push(GComm::Particle {
+                emitter: crate::game::particle::Emitter::HitSpark, 
+                position: phys.position, 
+                rotation: phys.rotation + std::f32::consts::PI, 
+                base_vel: [0., 0.].into(), 
+            });
+            let base_vel: nalgebra::Vector2<f32> = [
+                phys.vel_a / 2. * phys.rotation.cos(), 
+                phys.vel_a / 2. * phys.rotation.sin(), 
+            ].into();
+            if let Some(exp) = self.area_damage()
+                .map(|param| GComm::AreaDamage { 
+                    param, 
+                    position: phys.position, 
+                    base_vel, 
+                })
+                .or_else(|| self.explode().map(|param| GComm::Explode { 
+                    param, 
+                    position: phys.position, 
+                    base_vel, 
+                })
+            ) {
+                gcomm.push(exp)
+            }
+            if let Some(cl) = cluster {
+                gcomm.push(cl)
+            }
+            false
+        } else {
+            varea.in_visible(
+                phys.position, 
+                self.size()
+            )
         }
     }
 }
@@ -271,6 +610,8 @@ impl super::super::GTypeTrait for MissileGearType {
 pub struct LightMissile {
     target: Option<enemy::enemy::EnemyRef>, 
     fcs_controlled: bool, 
+    /// 発射の形式ごとに選ぶ目標の選択方式
+    strategy: choice::TargetStrategy, 
 }
 impl MissileTrait for LightMissile {
     fn mode(&self) -> MissileHomingMode {
@@ -286,73 +627,253 @@ impl MissileTrait for LightMissile {
     }
 
     fn rotation_speed(&self) -> f32 {
-        120.
+        240.
+    }
+
+    fn nav_const(&self) -> f32 {
+        4.
     }
 
     fn speed_boost_max(&self) -> (f32, f32) {
         (620., 1280.)
     }
 
+    fn strategy(&self) -> choice::TargetStrategy {
+        self.strategy
+    }
+
     fn seek_target(
         &mut self, 
         phys: super::super::GPhysWrap, 
         _ferris: &crate::game::ferris::ferris::FerrisBody, 
         enemies: &enemy::enemy::EnemyArray, 
+        varea: &simple2d::types::VisibleField, 
+        engage: &mut crate::game::enemy::engage::EngagementRegistry, 
     ) {
-        let fnc = |phys: &super::super::GPhysWrap, e: &enemy::enemy::Enemy| {
-            let dist = e.position - phys.position();
+        choice::seek_cone(
+            &mut self.target, 
+            self.fcs_controlled, 
+            self.strategy(), 
+            &phys, 
+            enemies, 
+            varea, 
+            engage, 
+            800., 
+            std::f32::consts::PI * 0.167, 
+        )
+    }
+}
+
+/// 重ミサイル
+/// 低速だが威力と爆発が大きい
+#[derive(Clone)]
+pub struct HeavyMissile {
+    target: Option<enemy::enemy::EnemyRef>, 
+    fcs_controlled: bool, 
+}
+impl MissileTrait for HeavyMissile {
+    fn mode(&self) -> MissileHomingMode {
+        MissileHomingMode::LeadNavigate
+    }
+
+    fn target(&self) -> Option<&enemy::enemy::EnemyRef> {
+        self.target.as_ref()
+    }
+
+    fn target_mut(&mut self) -> &mut Option<enemy::enemy::EnemyRef> {
+        &mut self.target
+    }
+
+    fn rotation_speed(&self) -> f32 {
+        60.
+    }
+
+    fn speed_boost_max(&self) -> (f32, f32) {
+        (240., 640.)
+    }
+
+    fn strategy(&self) -> choice::TargetStrategy {
+        choice::TargetStrategy::HighestScore
+    }
+
+    fn seek_target(
+        &mut self, 
+        phys: super::super::GPhysWrap, 
+        _ferris: &crate::game::ferris::ferris::FerrisBody, 
+        enemies: &enemy::enemy::EnemyArray, 
+        varea: &simple2d::types::VisibleField, 
+        engage: &mut crate::game::enemy::engage::EngagementRegistry, 
+    ) {
+        choice::seek_cone(
+            &mut self.target, 
+            self.fcs_controlled, 
+            self.strategy(), 
+            &phys, 
+            enemies, 
+            varea, 
+            engage, 
+            960., 
+            std::f32::consts::PI * 0.125, 
+        )
+    }
+}
+
+/// クラスター・ミサイル
+/// 目標の手前で子弾を散布する
+#[derive(Clone)]
+pub struct ClusterMissile {
+    target: Option<enemy::enemy::EnemyRef>, 
+    fcs_controlled: bool, 
+}
+impl MissileTrait for ClusterMissile {
+    fn mode(&self) -> MissileHomingMode {
+        MissileHomingMode::PureNavigate
+    }
+
+    fn target(&self) -> Option<&enemy::enemy::EnemyRef> {
+        self.target.as_ref()
+    }
+
+    fn target_mut(&mut self) -> &mut Option<enemy::enemy::EnemyRef> {
+        &mut self.target
+    }
+
+    fn rotation_speed(&self) -> f32 {
+        90.
+    }
+
+    fn speed_boost_max(&self) -> (f32, f32) {
+        (420., 960.)
+    }
+
+    fn strategy(&self) -> choice::TargetStrategy {
+        choice::TargetStrategy::MostUrgent
+    }
+
+    fn seek_target(
+        &mut self, 
+        phys: super::super::GPhysWrap, 
+        _ferris: &crate::game::ferris::ferris::FerrisBody, 
+        enemies: &enemy::enemy::EnemyArray, 
+        varea: &simple2d::types::VisibleField, 
+        engage: &mut crate::game::enemy::engage::EngagementRegistry, 
+    ) {
+        choice::seek_cone(
+            &mut self.target, 
+            self.fcs_controlled, 
+            self.strategy(), 
+            &phys, 
+            enemies, 
+            varea, 
+            engage, 
+            800., 
+            std::f32::consts::PI * 0.167, 
+        )
+    }
+}
+
+/// スウォーム・ミサイル
+/// 前方の近距離にいる目標を、群れの中で分散して追尾する
+#[derive(Clone)]
+pub struct SwarmMissile {
+    target: Option<enemy::enemy::EnemyRef>, 
+    /// 群れの中での順番
+    slot: u32, 
+}
+impl MissileTrait for SwarmMissile {
+    fn mode(&self) -> MissileHomingMode {
+        MissileHomingMode::PureNavigate
+    }
+
+    fn target(&self) -> Option<&enemy::enemy::EnemyRef> {
+        self.target.as_ref()
+    }
+
+    fn target_mut(&mut self) -> &mut Option<enemy::enemy::EnemyRef> {
+        &mut self.target
+    }
 
+    fn rotation_speed(&self) -> f32 {
+        // 1秒で4周くらい
+        1440.
+    }
+
+    fn speed_boost_max(&self) -> (f32, f32) {
+        (400., 900.)
+    }
+
+    fn strategy(&self) -> choice::TargetStrategy {
+        // 近い順に並べた候補を群れの中で分け合う
+        choice::TargetStrategy::Nearest
+    }
+
+    fn seek_target(
+        &mut self, 
+        phys: super::super::GPhysWrap, 
+        _ferris: &crate::game::ferris::ferris::FerrisBody, 
+        enemies: &enemy::enemy::EnemyArray, 
+        varea: &simple2d::types::VisibleField, 
+        engage: &mut crate::game::enemy::engage::EngagementRegistry, 
+    ) {
+        // 前方30度の400px以内にいるやつだけ狙う
+        let in_range = |phys: &super::super::GPhysWrap, e: &enemy::enemy::Enemy| {
+            let dist = e.position - phys.position();
             let angle = f32::atan2(dist.y, dist.x);
-            // 相対角度を計算
             let angle_diff = (
                 (angle - phys.rotation()) + std::f32::consts::PI
             ).rem_euclid(std::f32::consts::PI * 2.).abs()
                 - std::f32::consts::PI;
-
-            // 相対距離の計算
             let dist = (dist.x.powi(2) + dist.y.powi(2)).sqrt();
-
-            dist.abs() < 800. && angle_diff.abs() < std::f32::consts::PI * 0.167
+            dist <= 400. && angle_diff.abs() <= 30. * (std::f32::consts::PI / 180.)
         };
-        match &mut self.target {
-            e @ None => *e = choice::choice_simple_neerest(
-                &phys, 
-                enemies, 
-                fnc, 
-            ), 
-            e @ Some(_) if !self.fcs_controlled => if let Some(
-                tgt
-            ) = enemies.get(
-                e.as_ref().unwrap()
-            ) {
-                let dist = tgt.position - phys.phys.position;
-                let angle = f32::atan2(dist.y, dist.x);
-                let angle_diff = (
-                    (angle - phys.phys.rotation) + std::f32::consts::PI
-                ).rem_euclid(std::f32::consts::PI * 2.).abs()
-                    - std::f32::consts::PI;
-                if !(angle_diff.abs() < std::f32::consts::PI * 0.33) {
-                    *e = None
+        match &self.target {
+            None => {
+                self.target = choice::choice_spread(
+                    self.strategy(), 
+                    &phys, 
+                    enemies, 
+                    varea, 
+                    engage, 
+                    400., 
+                    in_range, 
+                    self.slot, 
+                );
+            }, 
+            // 角度・距離が大きすぎたら追尾解除
+            Some(t) => if let Some(tgt) = enemies.get(t) {
+                if !in_range(&phys, tgt) {
+                    self.target = None
                 }
-            }
-            _ => {}, 
+            }, 
         }
     }
 }
 
 /// ミサイルの実装
 pub trait MissileTrait {
+    /// 誘導方式
     fn mode(&self) -> MissileHomingMode;
     fn target(&self) -> Option<&enemy::enemy::EnemyRef>;
     fn target_mut(&mut self) -> &mut Option<enemy::enemy::EnemyRef>;
+
+    /// 旋回限界(度毎秒)
     fn rotation_speed(&self) -> f32;
+
+    /// 航法定数(比例誘導でのみ使用)
+    fn nav_const(&self) -> f32 { 3. }
+
     fn speed_boost_max(&self) -> (f32, f32);
 
+    /// 目標の選択方式
+    fn strategy(&self) -> choice::TargetStrategy;
+
     fn seek_target(
         &mut self, 
         phys: super::super::GPhysWrap, 
         ferris: &crate::game::ferris::ferris::FerrisBody, 
         enemies: &enemy::enemy::EnemyArray, 
+        varea: &simple2d::types::VisibleField, 
+        engage: &mut crate::game::enemy::engage::EngagementRegistry, 
     );
 
     fn remove_none_target(
@@ -378,33 +899,14 @@ pub trait MissileTrait {
             .map(|t| enemies.get(t))
             .flatten()
         {
-
-            let target = self.mode()
-                .calc_homing_v(&phys, tgt);
-
-            // 対象の絶対角度を計算
-            let angle = f32::atan2(
-                target.y, target.x
+            let rotation = self.mode().steer(
+                self.nav_const(), 
+                self.rotation_speed() * (std::f32::consts::PI / 180.), 
+                &phys, 
+                tgt, 
+                cycle.dur, 
             );
-
-            // 相対角度を計算
-            let angle_diff = (
-                (angle - phys.phys.rotation) + std::f32::consts::PI
-            ).rem_euclid(std::f32::consts::PI * 2.).abs()
-                - std::f32::consts::PI;
-            
-            // 回転速度
-            let rs = self.rotation_speed() 
-                * (std::f32::consts::PI / 180.) 
-                * cycle.dur;
-
-            if angle_diff <= rs {
-                phys.phys.rotation -= rs;
-            } else if rs <= angle_diff {
-                phys.phys.rotation += rs;
-            } else {
-                phys.phys.rotation = angle
-            }
+            phys.phys.rotation = rotation;
         }
     }
 }
\ No newline at end of file
@@ -28,6 +28,34 @@ pub enum GType {
     /// なんかしらのエフェクト
     Fragment(fragment::FragmentGear), 
 }
+impl GType {
+    /// 交戦中の目標と、命中時に見込まれるダメージの種類と量
+    pub fn engagement(&self) -> Option<(
+        &crate::game::enemy::enemy::EnemyRef, 
+        crate::game::enemy::damage::DamageType, 
+        f32, 
+    )> { match self {
+        GType::GunShot(gs) => gs.target().map(|t| (t, gs.ty.damage_type(), gs.ty.damage())), 
+        GType::Missile(gm) => gm.missile().target()
+            .map(|t| (t, crate::game::enemy::damage::DamageType::Explosive, gm.damage())), 
+        GType::Fragment(_) => None, 
+    }}
+
+    /// 当たり判定の形状
+    /// rotationは進行方向
+    pub fn shape(&self, rotation: f32) -> crate::game::shape::Shape {
+        let size = self.size();
+        match self {
+            GType::GunShot(gs) => gs.ty.shape(), 
+            GType::Missile(_) => crate::game::shape::Shape::Capsule { 
+                half_len: size.x * 0.25, 
+                radius: size.y * 0.25, 
+                rotation, 
+            }, 
+            GType::Fragment(_) => crate::game::shape::Shape::Aabb { size }, 
+        }
+    }
+}
 impl GTypeTrait for GType {
     fn angle_diff(&self) -> Option<std::ops::Range<f32>> { match self {
         GType::GunShot(gs) => gs.angle_diff(),
@@ -71,6 +99,8 @@ impl GTypeTrait for GType {
         >, 
         enemies: &mut EnemyArray, 
         gcomm: &mut super::gcomm::GCommQueue, 
+        engage: &mut crate::game::enemy::engage::EngagementRegistry, 
+        feedback: &mut crate::game::feedback::Feedback, 
     ) -> bool { match self {
         GType::GunShot(gs) => gs.update(
             cycle, 
@@ -81,6 +111,8 @@ impl GTypeTrait for GType {
             aim, 
             enemies, 
             gcomm, 
+            engage, 
+            feedback, 
         ),
         GType::Missile(gm) => gm.update(
             cycle, 
@@ -91,6 +123,8 @@ impl GTypeTrait for GType {
             aim, 
             enemies, 
             gcomm, 
+            engage, 
+            feedback, 
         ),
         GType::Fragment(gf) => gf.update(
             cycle, 
@@ -101,6 +135,8 @@ impl GTypeTrait for GType {
             aim, 
             enemies, 
             gcomm, 
+            engage, 
+            feedback, 
         ),
     }}
 }
@@ -159,6 +195,7 @@ pub trait GTypeTrait {
         );
         super::GearPhys {
             position, 
+            prev_position: position, 
             vel_a, 
             rotation, 
         }
@@ -195,5 +232,7 @@ pub trait GTypeTrait {
         >, 
         enemies: &mut EnemyArray, 
         gcomm: &mut super::gcomm::GCommQueue, 
+        engage: &mut crate::game::enemy::engage::EngagementRegistry, 
+        feedback: &mut crate::game::feedback::Feedback, 
     ) -> bool;
 }
\ No newline at end of file
@@ -8,7 +8,10 @@ use tm_wg_wrapper::{
         img_obj::ImgObjInstance, physic::PhysicBody, entity_holder::EntityHolder, 
     }
 };
-use crate::game::enemy::enemy::EnemyArray;
+use crate::game::{
+    enemy::enemy::EnemyArray, 
+    shape::{Collider, HasShape}, 
+};
 pub mod array;
 pub mod gtype;
 use gtype::GTypeTrait;
@@ -17,6 +20,8 @@ pub mod gcomm;
 /// ギアの物理的な値
 pub struct GearPhys {
     pub position: nalgebra::Point2<f32>,  
+    /// 直前の移動を始めた位置
+    pub prev_position: nalgebra::Point2<f32>, 
     pub rotation: f32, 
     pub vel_a: f32, 
 }
@@ -25,6 +30,7 @@ impl GearPhys {
         &mut self, 
         cycle: &cycle_measure::CycleMeasure, 
     ) {
+        self.prev_position = self.position;
         let vel = self.vel_a * cycle.dur;
         self.position += nalgebra::Vector2::from([
             vel * self.rotation.cos(), 
@@ -55,7 +61,7 @@ impl InstanceGen<ImgObjInstance> for GearInstance {
             position: self.gb.phys.position.into(), 
             size: self.gb.gt.size().into(), 
             rotation: self.gb.tex_rot, 
-            tex_coord: [0., 0.], 
+            tex_coord: crate::atlas::locate("gear", [0., 0.]), 
             tex_size: [32., 32.], 
             tex_rev: [false, false] 
         })
@@ -81,6 +87,14 @@ impl PhysicBody for GearInstance {
         ].into()
     }
 }
+impl HasShape for GearInstance {
+    fn collider(&self) -> Collider {
+        Collider {
+            position: self.gb.phys.position, 
+            shape: self.gb.gt.shape(self.gb.phys.rotation), 
+        }
+    }
+}
 impl GearInstance {
     pub fn update(
         &mut self, 
@@ -90,6 +104,8 @@ impl GearInstance {
         aim: &EntityHolder<ImgObjInstance, super::aim::Aim>, 
         enemies: &mut EnemyArray, 
         gcomm: &mut gcomm::GCommQueue, 
+        engage: &mut crate::game::enemy::engage::EngagementRegistry, 
+        feedback: &mut crate::game::feedback::Feedback, 
     ) -> bool {
         let res = self.gb.gt.update(
             cycle, 
@@ -100,6 +116,8 @@ impl GearInstance {
             aim, 
             enemies, 
             gcomm, 
+            engage, 
+            feedback, 
         );
         self.gb.phys.update(cycle);
         self.gb.tex_rot += self.gb.tex_rot_speed * cycle.dur;
@@ -136,6 +154,15 @@ impl PhysicBody for GPhysWrap<'_, '_> {
     }
 }
 
+impl HasShape for GPhysWrap<'_, '_> {
+    fn collider(&self) -> Collider {
+        Collider {
+            position: self.phys.position, 
+            shape: self.gt.shape(self.phys.rotation), 
+        }
+    }
+}
+
 /// ギアの物理演算用ラップ(可変)
 pub struct GPhysWrapMut<'a, 'b> {
     gt: &'a gtype::GType, 
@@ -0,0 +1,284 @@
+//! 画面の情報表示の実装
+//! 位置は画面の隅を基準に指定し、解像度が変わっても隅に張り付くようにする
+
+use std::fmt::Write;
+
+use super::*;
+use ferris::ngear::gtype::gun::GTToggle;
+
+/// 文字の大きさの倍率
+pub const TEXT_RATIO: f32 = 0.75;
+
+/// 文字の行の高さ
+pub const LINE_HEIGHT: f32 = 32. * TEXT_RATIO;
+
+/// ゲージの大きさ
+pub const GAUGE_SIZE: [f32; 2] = [160., 8.];
+
+/// 体力ゲージの大きさ
+pub const HEALTH_GAUGE_SIZE: [f32; 2] = [240., 12.];
+
+/// 画面の端からの余白
+pub const MARGIN: f32 = 12.;
+
+/// 描画時点の論理的な画面の範囲
+/// 画面の中心と大きさはカメラから求めるため、カメラ演出で揺れても画面上の位置は変わらない
+#[derive(Clone, Copy)]
+pub struct Screen {
+    pub center: [f32; 2], 
+    pub half: [f32; 2], 
+}
+impl Screen {
+    pub fn from_camera(camera: &simple2d::types::Camera) -> Self {
+        // 帯を除いた、論理的な画面が映る範囲
+        let ratio = crate::viewport::current().scale() / camera.zoom;
+        let half = crate::viewport::LOGICAL_SIZE.map(|s| s * 0.5 * ratio);
+        Self {
+            center: [camera.position.x, camera.position.y], 
+            half, 
+        }
+    }
+}
+
+/// 画面上の基準位置
+#[derive(Clone, Copy)]
+pub enum Anchor {
+    TopLeft, 
+    TopRight, 
+    BottomLeft, 
+    BottomRight, 
+}
+impl Anchor {
+    /// 画面の隅からoffsetだけ内側の位置
+    pub fn position(
+        &self, 
+        screen: &Screen, 
+        offset: [f32; 2], 
+    ) -> [f32; 2] {
+        let (sx, sy) = self.sign();
+        [
+            screen.center[0] + sx * (screen.half[0] - offset[0]), 
+            screen.center[1] + sy * (screen.half[1] - offset[1]), 
+        ]
+    }
+
+    /// 画面の中心から見た隅の方向
+    fn sign(&self) -> (f32, f32) { match self {
+        Anchor::TopLeft => (-1., 1.), 
+        Anchor::TopRight => (1., 1.), 
+        Anchor::BottomLeft => (-1., -1.), 
+        Anchor::BottomRight => (1., -1.), 
+    }}
+
+    pub fn align_h(&self) -> simple2d::font_typing::TypeAlignH { match self {
+        Anchor::TopLeft
+        | Anchor::BottomLeft => simple2d::font_typing::TypeAlignH::Left, 
+        Anchor::TopRight
+        | Anchor::BottomRight => simple2d::font_typing::TypeAlignH::Right, 
+    }}
+
+    pub fn align_v(&self) -> simple2d::font_typing::TypeAlignV { match self {
+        Anchor::TopLeft
+        | Anchor::TopRight => simple2d::font_typing::TypeAlignV::Top, 
+        Anchor::BottomLeft
+        | Anchor::BottomRight => simple2d::font_typing::TypeAlignV::Bottom, 
+    }}
+
+    /// 隅から内側へ幅widthの要素を置いた時の左端
+    fn left(&self, x: f32, width: f32) -> f32 { match self {
+        Anchor::TopLeft
+        | Anchor::BottomLeft => x, 
+        Anchor::TopRight
+        | Anchor::BottomRight => x - width, 
+    }}
+}
+
+/// 残量を示すゲージ
+pub struct Gauge {
+    /// 左端の中心
+    pub left: [f32; 2], 
+    pub size: [f32; 2], 
+    pub ratio: f32, 
+}
+impl InstanceGen<ImgObjInstance> for Gauge {
+    fn generate(
+        &self, 
+        instances: &mut simple2d::instance::buffer::InstanceArray<ImgObjInstance>
+    ) {
+        // 背景
+        instances.push(ImgObjInstance {
+            position: [self.left[0] + self.size[0] * 0.5, self.left[1]], 
+            size: self.size, 
+            rotation: 0., 
+            tex_coord: crate::atlas::locate("bar", [0., 8.]), 
+            tex_size: [16., 8.], 
+            tex_rev: [false, false], 
+        });
+        // 残量
+        let width = self.size[0] * self.ratio.clamp(0., 1.);
+        instances.push(ImgObjInstance {
+            position: [self.left[0] + width * 0.5, self.left[1]], 
+            size: [width, self.size[1]], 
+            rotation: 0., 
+            tex_coord: crate::atlas::locate("bar", [0., 0.]), 
+            tex_size: [16., 8.], 
+            tex_rev: [false, false], 
+        });
+    }
+}
+
+/// 画面の情報表示
+pub struct Hud {
+    status: String, 
+    weapon: String, 
+    gun_state: String, 
+    missile: String, 
+    modes: String, 
+    health: f32, 
+    shield: f32, 
+    gun_ready: f32, 
+    heat: Option<f32>, 
+    missile_ready: f32, 
+}
+impl Hud {
+    pub fn new() -> Self { Self {
+        status: String::new(), 
+        weapon: String::new(), 
+        gun_state: String::new(), 
+        missile: String::new(), 
+        modes: String::new(), 
+        health: 1., 
+        shield: 0., 
+        gun_ready: 1., 
+        heat: None, 
+        missile_ready: 1., 
+    }}
+
+    /// 表示内容の更新
+    pub fn update(
+        &mut self, 
+        score: u64, 
+        health: u64, 
+        shield: u64, 
+        ferris: Option<&ferris::ferris::Ferris>, 
+    ) -> std::fmt::Result {
+        self.health = health as f32 / HEALTH_MAX as f32;
+        self.shield = shield as f32 / SHIELD_MAX as f32;
+        self.status.clear();
+        write!(
+            self.status, 
+            "Score: {score}\nHealth: {health}\nShield: {shield}", 
+            score = score, 
+            health = health, 
+            shield = shield, 
+        )?;
+
+        let ferris = if let Some(ferris) = ferris { ferris } else { return Ok(()) };
+
+        // 砲
+        let gg = &ferris.gg2;
+        self.weapon.clear();
+        write!(
+            self.weapon, 
+            "< {prev}\n[{cur}]\n> {next}", 
+            prev = gg.neighbor(GTToggle::Backward).name(), 
+            cur = gg.gt.name(), 
+            next = gg.neighbor(GTToggle::Forward).name(), 
+        )?;
+        self.gun_ready = 1. - gg.ct() / gg.gt.cool_time();
+        let state = gg.state();
+        self.heat = gg.gt.heat().map(|_| state.heat);
+        self.gun_state.clear();
+        if let Some(rl) = state.reload {
+            write!(self.gun_state, "RELOAD {:.1}s", rl)?
+        } else if state.overheat {
+            write!(self.gun_state, "OVERHEAT")?
+        } else if let (Some(ammo), Some(mag)) = (state.ammo, gg.gt.magazine()) {
+            write!(self.gun_state, "AMMO {}/{}", ammo, mag.size)?
+        } else {
+            write!(self.gun_state, "AMMO --")?
+        }
+
+        // ミサイル
+        let ml = &ferris.ml;
+        self.missile.clear();
+        write!(self.missile, "{} x{}", ml.lt.name(), ml.ammo())?;
+        self.missile_ready = 1. - ml.ct() / ml.lt.cool_time();
+
+        // 操作の状態
+        self.modes.clear();
+        write!(
+            self.modes, 
+            "FUZE:{fuze} TRACK:{track}", 
+            fuze = if ferris.control.time_fuze.is_triggered() { "ON" } else { "OFF" }, 
+            track = if ferris.control.manual_track.is_latch_on() { "MANUAL" } else { "AUTO" }, 
+        )
+    }
+
+    pub fn rendering(&self, renderer: &mut crate::renderer::FSRenderer) {
+        let screen = Screen::from_camera(&renderer.camera.camera);
+
+        // 右上: スコア・体力
+        self.text(renderer, &screen, Anchor::TopRight, [MARGIN, MARGIN], &self.status);
+
+        // 左上: 体力・シールドのゲージ
+        self.gauge(renderer, &screen, Anchor::TopLeft, [MARGIN, MARGIN + 6.], HEALTH_GAUGE_SIZE, self.health);
+        self.gauge(renderer, &screen, Anchor::TopLeft, [MARGIN, MARGIN + 22.], GAUGE_SIZE, self.shield);
+
+        // 左下: 砲
+        let mut y = MARGIN;
+        self.text(renderer, &screen, Anchor::BottomLeft, [MARGIN, y], &self.gun_state);
+        y += LINE_HEIGHT + 6.;
+        if let Some(heat) = self.heat {
+            self.gauge(renderer, &screen, Anchor::BottomLeft, [MARGIN, y], GAUGE_SIZE, heat);
+            y += 12.;
+        }
+        self.gauge(renderer, &screen, Anchor::BottomLeft, [MARGIN, y], GAUGE_SIZE, self.gun_ready);
+        y += 10.;
+        self.text(renderer, &screen, Anchor::BottomLeft, [MARGIN, y], &self.weapon);
+
+        // 右下: ミサイル・操作の状態
+        let mut y = MARGIN;
+        self.text(renderer, &screen, Anchor::BottomRight, [MARGIN, y], &self.modes);
+        y += LINE_HEIGHT + 6.;
+        self.gauge(renderer, &screen, Anchor::BottomRight, [MARGIN, y], GAUGE_SIZE, self.missile_ready);
+        y += 10.;
+        self.text(renderer, &screen, Anchor::BottomRight, [MARGIN, y], &self.missile);
+    }
+
+    fn text(
+        &self, 
+        renderer: &mut crate::renderer::FSRenderer, 
+        screen: &Screen, 
+        anchor: Anchor, 
+        offset: [f32; 2], 
+        s: &str, 
+    ) {
+        renderer.text(crate::render_queue::Layer::Hud).draw_type(&simple2d::font_typing::TypeParam {
+            s, 
+            position: anchor.position(screen, offset), 
+            rotation: 0., 
+            size_ratio: [TEXT_RATIO, TEXT_RATIO], 
+            align_v: anchor.align_v(), 
+            align_h: anchor.align_h(), 
+            direction: simple2d::font_typing::TypeDirection::Horizontal, 
+        });
+    }
+
+    fn gauge(
+        &self, 
+        renderer: &mut crate::renderer::FSRenderer, 
+        screen: &Screen, 
+        anchor: Anchor, 
+        offset: [f32; 2], 
+        size: [f32; 2], 
+        ratio: f32, 
+    ) {
+        let p = anchor.position(screen, offset);
+        renderer.sprite(crate::render_queue::Layer::Hud).push_instance(&Gauge {
+            left: [anchor.left(p[0], size[0]), p[1]], 
+            size, 
+            ratio, 
+        });
+    }
+}
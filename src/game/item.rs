@@ -0,0 +1,202 @@
+//! 撃破した敵が落とすアイテムの実装
+
+use rand::Rng;
+
+use super::*;
+
+/// アイテムの種類
+#[derive(Clone, Copy)]
+pub enum ItemType {
+    /// 体力の回復
+    Repair, 
+    /// ミサイルの補充
+    MissileAmmo, 
+    /// 一時的な連射速度の上昇
+    FireRateBoost, 
+    /// 取りこぼしのダメージを肩代わりするシールド
+    Shield, 
+    /// 新しい砲の解禁
+    /// 全て解禁済みであれば全ての砲の弾倉の補充と冷却
+    WeaponUnlock, 
+}
+impl ItemType {
+    pub fn tex_coord(&self) -> [f32; 2] { match self {
+        ItemType::Repair => [0., 0.], 
+        ItemType::MissileAmmo => [32., 0.], 
+        ItemType::FireRateBoost => [64., 0.], 
+        ItemType::Shield => [96., 0.], 
+        ItemType::WeaponUnlock => [128., 0.], 
+    }}
+
+    pub fn tex_size(&self) -> [f32; 2] { match self {
+        _ => [32., 32.], 
+    }}
+
+    pub fn size(&self) -> nalgebra::Vector2<f32> { match self {
+        _ => [32., 32.].into(), 
+    }}
+
+    /// 落下速度
+    pub fn fall_vel(&self) -> f32 { match self {
+        ItemType::Repair => 120., 
+        ItemType::MissileAmmo => 140., 
+        ItemType::FireRateBoost => 160., 
+        ItemType::Shield => 120., 
+        ItemType::WeaponUnlock => 100., 
+    }}
+
+    /// 横方向の漂いの幅
+    pub fn drift_diffuse(&self) -> Option<std::ops::Range<f32>> { match self {
+        _ => Some(-40.0..40.0), 
+    }}
+
+    /// アイテムの効果を適用する
+    pub fn apply(
+        &self, 
+        ferris: &mut ferris::ferris::Ferris, 
+        health: &mut u64, 
+        shield: &mut u64, 
+    ) { match self {
+        ItemType::Repair => *health = (*health + 150).min(HEALTH_MAX), 
+        ItemType::MissileAmmo => ferris.ml.add_ammo(8), 
+        ItemType::FireRateBoost => ferris.gg2.give_boost(8.), 
+        ItemType::Shield => *shield = (*shield + 200).min(SHIELD_MAX), 
+        ItemType::WeaponUnlock => if !ferris.gg2.unlock_next() {
+            ferris.gg2.resupply()
+        }, 
+    }}
+
+    /// ドロップ表からアイテムを抽選する
+    pub fn roll(
+        rng: &mut impl Rng, 
+        table: &[(ItemType, f64)], 
+    ) -> Option<ItemType> {
+        let r = rng.gen_range(0.0..1.0);
+        let mut acc = 0.;
+        for (it, chance) in table {
+            acc += chance;
+            if r < acc { return Some(*it) }
+        }
+        None
+    }
+
+    pub fn spawn(
+        self, 
+        position: nalgebra::Point2<f32>, 
+    ) -> Item {
+        let drift = crate::RNG.with(|r| self.drift_diffuse()
+            .map_or(0., |d| r.borrow_mut().gen_range(d))
+        );
+        Item {
+            item_type: self, 
+            position, 
+            velocity: [drift, -self.fall_vel()].into(), 
+        }
+    }
+}
+
+/// アイテムの実体
+pub struct Item {
+    item_type: ItemType, 
+    position: nalgebra::Point2<f32>, 
+    velocity: nalgebra::Vector2<f32>, 
+}
+impl Item {
+    pub fn update(
+        &mut self, 
+        cycle: &cycle_measure::CycleMeasure, 
+        varea: &VisibleField, 
+    ) -> bool {
+        self.position += self.velocity * cycle.dur;
+        let va = varea.visible_area();
+        if self.position.x < va[0].x {
+            self.position.x = va[0].x;
+            self.velocity.x = self.velocity.x.abs();
+        } else if va[1].x < self.position.x {
+            self.position.x = va[1].x;
+            self.velocity.x = -self.velocity.x.abs();
+        }
+        va[0].y <= self.position.y
+    }
+}
+impl shape::HasShape for Item {
+    fn collider(&self) -> shape::Collider {
+        shape::Collider {
+            position: self.position, 
+            shape: shape::Shape::Circle { radius: self.item_type.size().x * 0.5 }, 
+        }
+    }
+}
+impl physic::PhysicBody for Item {
+    fn position(&self) -> nalgebra::Point2<f32> {
+        self.position
+    }
+
+    fn size(&self) -> nalgebra::Vector2<f32> {
+        self.item_type.size()
+    }
+
+    fn rotation(&self) -> f32 {
+        0.
+    }
+
+    fn velocity(&self) -> nalgebra::Vector2<f32> {
+        self.velocity
+    }
+}
+impl InstanceGen<ImgObjInstance> for Item {
+    fn generate(
+        &self, 
+        instances: &mut simple2d::instance::buffer::InstanceArray<ImgObjInstance>
+    ) {
+        instances.push(ImgObjInstance {
+            position: self.position.into(), 
+            size: self.item_type.size().into(), 
+            rotation: 0., 
+            tex_coord: crate::atlas::locate("item", self.item_type.tex_coord()), 
+            tex_size: self.item_type.tex_size(), 
+            tex_rev: [false, false], 
+        })
+    }
+}
+
+/// アイテムを格納する配列
+pub struct ItemArray {
+    pub items: EntityArray<ImgObjInstance, Item>, 
+}
+impl ItemArray {
+    pub fn new() -> Self { Self {
+        items: EntityArray::new([]), 
+    }}
+
+    pub fn push(&mut self, item: Item) {
+        self.items.push(item);
+    }
+
+    pub fn update(
+        &mut self, 
+        cycle: &cycle_measure::CycleMeasure, 
+        varea: &VisibleField, 
+    ) {
+        self.items.retain(|_idx, item| item.update(cycle, varea));
+    }
+
+    /// Ferrisに接触したアイテムを回収する
+    pub fn collect(
+        &mut self, 
+        ferris: &mut ferris::ferris::Ferris, 
+        health: &mut u64, 
+        shield: &mut u64, 
+    ) {
+        self.items.retain(|_idx, item| if shape::overlap(item, &ferris.body) {
+            item.item_type.apply(ferris, health, shield);
+            false
+        } else {
+            true
+        });
+    }
+
+    pub fn rendering(&self, renderer: &mut crate::renderer::FSRenderer) {
+        renderer.sprite(crate::render_queue::Layer::Enemies).push_instance(&self.items);
+    }
+}
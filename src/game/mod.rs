@@ -1,5 +1,3 @@
-use std::fmt::Write;
-
 use tm_wg_wrapper::{
     prelude::*, 
     util::control::{
@@ -24,25 +22,45 @@ use tm_wg_wrapper::{
 use crate::{game_pause::GamePause, game_over::GameOver};
 pub mod ferris;
 pub mod enemy;
+pub mod item;
+pub mod particle;
+pub mod anim;
+pub mod def_file;
+pub mod camera_fx;
+pub mod hud;
+pub mod background;
+pub mod feedback;
+pub mod shape;
+pub mod sweep;
+
+/// 体力の最大値
+pub const HEALTH_MAX: u64 = 1000;
+
+/// シールドの最大値
+pub const SHIELD_MAX: u64 = 500;
 
 pub struct Game {
     input_esc: TrigTimeWrap<Trigger>, 
     input_p: Trigger, 
+    input_fullscreen: Trigger, 
     is_top_prev: bool, 
     elements: Elements, 
     score: u64, 
     health: u64, 
-    ui_text_buffer: Option<String>, 
+    shield: u64, 
+    hud: hud::Hud, 
 }
 impl Game {
     pub fn new() -> Self { Self {
         input_esc: TrigTimeWrap { ctrl: Trigger::default(), input_dur: 0. },  
         input_p: Trigger::default(), 
+        input_fullscreen: Trigger::default(), 
         is_top_prev: false, 
         elements: Elements::new(),
         score: 0, 
-        health: 1000, 
-        ui_text_buffer: None, 
+        health: HEALTH_MAX, 
+        shield: 0, 
+        hud: hud::Hud::new(), 
     }}
 
     pub fn update(
@@ -69,26 +87,27 @@ impl Game {
                 varea, 
                 &mut self.score, 
                 &mut self.health, 
+                &mut self.shield, 
             );
         }
         self.input_esc.update(cycle);
         self.input_p.update();
+        self.input_fullscreen.update();
 
-        if let Some(stb) = self.ui_text_buffer.as_mut() {
-            stb.clear();
-            stb.write_fmt(format_args!(
-                "Score: {score}\nHealth: {health}", 
-                score = self.score, 
-                health = self.health, 
-            ))?
-        } else {
-            self.ui_text_buffer = Some(format!(
-                "Score: {score}\nHealth: {health}", 
-                score = self.score, 
-                health = self.health, 
-            ))
+        if self.input_fullscreen.get_trig_count() == 1 {
+            window.set_fullscreen(match window.fullscreen() {
+                Some(_) => None, 
+                None => Some(winit::window::Fullscreen::Borderless(None)), 
+            })
         }
 
+        self.hud.update(
+            self.score, 
+            self.health, 
+            self.shield, 
+            self.elements.ferris.ferris(), 
+        )?;
+
         if 0.5 < self.input_esc.input_dur() {
             Ok(scene_frame::SceneProcOp::StkCtl(
                 scene_frame::SceneStackCtrlOp::Exit
@@ -111,20 +130,8 @@ impl Game {
     }
 
     pub fn rendering(&self, renderer: &mut crate::renderer::FSRenderer) {
-        renderer.font.draw_type(&simple2d::font_typing::TypeParam {
-            s: self.ui_text_buffer.as_ref()
-                .map(|s| s.as_str())
-                .unwrap_or("Score: null\nHealth: null"),
-            position: {
-                let size = renderer.camera.camera.size;
-                (size / 2.).into()
-            },
-            rotation: 0.,
-            size_ratio: [1., 1.],
-            align_v: simple2d::font_typing::TypeAlignV::Top,
-            align_h: simple2d::font_typing::TypeAlignH::Right,
-            direction: simple2d::font_typing::TypeDirection::Horizontal,
-        });
+        self.elements.camera_fx.apply(&mut renderer.camera.camera);
+        self.hud.rendering(renderer);
         self.elements.rendering(renderer)
     }
 
@@ -136,8 +143,11 @@ impl Game {
         match keycode {
             VirtualKeyCode::Escape => self.input_esc.ctrl.trigger(state), 
             VirtualKeyCode::P => self.input_p.trigger(state), 
+            VirtualKeyCode::F11 => self.input_fullscreen.trigger(state), 
             _ => {}, 
         }
+        self.elements.feedback.input_key(keycode, state);
+        self.elements.camera_fx.input_key(keycode, state);
         self.elements.ferris.input_key(keycode, state)
     }
 
@@ -160,11 +170,21 @@ impl Game {
 pub struct Elements {
     ferris: ferris::FerrisInstances, 
     enemies: enemy::EnemyInstances, 
+    items: item::ItemArray, 
+    feedback: feedback::Feedback, 
+    particles: particle::ParticleSystem, 
+    camera_fx: camera_fx::CameraFx, 
+    background: background::Background, 
 }
 impl Elements {
     pub fn new() -> Self { Self {
         ferris: ferris::FerrisInstances::new(), 
         enemies: enemy::EnemyInstances::new(), 
+        items: item::ItemArray::new(), 
+        feedback: feedback::Feedback::new(), 
+        particles: particle::ParticleSystem::new(), 
+        camera_fx: camera_fx::CameraFx::new(), 
+        background: background::Background::new(), 
     }}
 
     pub fn update(
@@ -173,13 +193,26 @@ impl Elements {
         varea: &simple2d::types::VisibleField, 
         score: &mut u64, 
         health: &mut u64, 
+        shield: &mut u64, 
     ) {
-        self.ferris.update(cycle, varea, &mut self.enemies.enemy);
-        self.enemies.update(cycle, varea, score, health);
+        // 演出は実時間で進め、ゲームプレイは減速を反映した時間で進める
+        self.camera_fx.update(cycle);
+        let cycle = &self.camera_fx.scaled_cycle(cycle);
+        self.ferris.update(cycle, varea, &mut self.enemies.enemy, &mut self.particles, &mut self.camera_fx, &mut self.feedback);
+        self.enemies.update(cycle, varea, score, health, shield, &mut self.items, &mut self.feedback, &mut self.particles, &mut self.camera_fx);
+        self.background.update(cycle);
+        self.particles.update(cycle);
+        self.feedback.update(cycle);
+        self.items.update(cycle, varea);
+        self.ferris.collect_items(&mut self.items, health, shield);
     }
 
     pub fn rendering(&self, renderer: &mut crate::renderer::FSRenderer) {
+        self.background.rendering(renderer);
         self.ferris.rendering(renderer);
         self.enemies.rendering(renderer);
+        self.items.rendering(renderer);
+        self.particles.rendering(renderer);
+        self.feedback.rendering(renderer, &self.enemies.enemy);
     }
 }
@@ -0,0 +1,197 @@
+//! 見た目だけのパーティクルの実装
+//! 敵に一切干渉せず、ゲームバランスに影響しない
+
+use rand::Rng;
+
+use super::*;
+
+/// 同時に存在できるパーティクルの上限
+pub const PARTICLE_BUDGET: usize = 1536;
+
+/// パーティクルの発生源の種類
+#[derive(Clone, Copy)]
+pub enum Emitter {
+    /// 射撃時の発射炎
+    MuzzleFlash, 
+    /// 命中時の火花
+    HitSpark, 
+    /// 撃破時の破裂
+    DeathBurst, 
+    /// ミサイルの排気
+    Exhaust, 
+    /// 爆発で飛び散る破片
+    Debris, 
+}
+impl Emitter {
+    pub fn tex_coord(&self) -> [f32; 2] { match self {
+        Emitter::MuzzleFlash => [0., 0.], 
+        Emitter::HitSpark => [32., 0.], 
+        Emitter::DeathBurst => [64., 0.], 
+        Emitter::Exhaust => [96., 0.], 
+        Emitter::Debris => [32., 0.], 
+    }}
+
+    pub fn tex_size(&self) -> [f32; 2] { match self {
+        _ => [32., 32.], 
+    }}
+
+    /// 一度に発生させる数
+    pub fn count(&self) -> u32 { match self {
+        Emitter::MuzzleFlash => 3, 
+        Emitter::HitSpark => 6, 
+        Emitter::DeathBurst => 16, 
+        Emitter::Exhaust => 1, 
+        Emitter::Debris => 12, 
+    }}
+
+    /// 発生方向からの拡散角
+    pub fn spread(&self) -> f32 { match self {
+        Emitter::MuzzleFlash => 20. * (std::f32::consts::PI / 180.), 
+        Emitter::HitSpark => 60. * (std::f32::consts::PI / 180.), 
+        Emitter::DeathBurst => std::f32::consts::PI, 
+        Emitter::Exhaust => 10. * (std::f32::consts::PI / 180.), 
+        Emitter::Debris => std::f32::consts::PI, 
+    }}
+
+    /// 初速
+    pub fn speed(&self) -> std::ops::Range<f32> { match self {
+        Emitter::MuzzleFlash => 60.0..180.0, 
+        Emitter::HitSpark => 180.0..420.0, 
+        Emitter::DeathBurst => 80.0..260.0, 
+        Emitter::Exhaust => 40.0..80.0, 
+        Emitter::Debris => 240.0..640.0, 
+    }}
+
+    /// 寿命
+    pub fn life(&self) -> std::ops::Range<f32> { match self {
+        Emitter::MuzzleFlash => 0.05..0.1, 
+        Emitter::HitSpark => 0.1..0.25, 
+        Emitter::DeathBurst => 0.3..0.6, 
+        Emitter::Exhaust => 0.2..0.4, 
+        Emitter::Debris => 0.2..0.5, 
+    }}
+
+    /// 大きさ
+    pub fn size(&self) -> std::ops::Range<f32> { match self {
+        Emitter::MuzzleFlash => 12.0..20.0, 
+        Emitter::HitSpark => 4.0..8.0, 
+        Emitter::DeathBurst => 10.0..24.0, 
+        Emitter::Exhaust => 8.0..14.0, 
+        Emitter::Debris => 3.0..7.0, 
+    }}
+
+    /// 毎秒の減速率
+    pub fn drag(&self) -> f32 { match self {
+        Emitter::MuzzleFlash => 8., 
+        Emitter::HitSpark => 4., 
+        Emitter::DeathBurst => 2.5, 
+        Emitter::Exhaust => 1.5, 
+        Emitter::Debris => 3., 
+    }}
+
+    /// 寿命が尽きる時の大きさの倍率
+    pub fn end_scale(&self) -> f32 { match self {
+        Emitter::MuzzleFlash => 0.5, 
+        Emitter::HitSpark => 0.2, 
+        Emitter::DeathBurst => 1.6, 
+        Emitter::Exhaust => 2., 
+        Emitter::Debris => 0.5, 
+    }}
+}
+
+/// パーティクルの実体
+pub struct Particle {
+    emitter: Emitter, 
+    position: nalgebra::Point2<f32>, 
+    velocity: nalgebra::Vector2<f32>, 
+    rotation: f32, 
+    size: f32, 
+    life: f32, 
+    life_max: f32, 
+}
+impl Particle {
+    pub fn update(&mut self, cycle: &cycle_measure::CycleMeasure) -> bool {
+        self.position += self.velocity * cycle.dur;
+        self.velocity *= (1. - self.emitter.drag() * cycle.dur).max(0.);
+        self.life -= cycle.dur;
+        0. < self.life
+    }
+}
+impl InstanceGen<ImgObjInstance> for Particle {
+    fn generate(
+        &self, 
+        instances: &mut simple2d::instance::buffer::InstanceArray<ImgObjInstance>
+    ) {
+        let t = 1. - self.life / self.life_max;
+        let size = self.size * (1. + (self.emitter.end_scale() - 1.) * t);
+        instances.push(ImgObjInstance {
+            position: self.position.into(), 
+            size: [size, size], 
+            rotation: self.rotation, 
+            tex_coord: crate::atlas::locate("particle", self.emitter.tex_coord()), 
+            tex_size: self.emitter.tex_size(), 
+            tex_rev: [false, false], 
+        })
+    }
+}
+
+/// パーティクルを格納する配列
+pub struct ParticleSystem {
+    particles: EntityArray<ImgObjInstance, Particle>, 
+    count: usize, 
+}
+impl ParticleSystem {
+    pub fn new() -> Self { Self {
+        particles: EntityArray::new([]), 
+        count: 0, 
+    }}
+
+    /// パーティクルを発生させる
+    /// 上限に達している場合は発生させない
+    pub fn emit(
+        &mut self, 
+        emitter: Emitter, 
+        position: nalgebra::Point2<f32>, 
+        rotation: f32, 
+        base_vel: nalgebra::Vector2<f32>, 
+    ) {
+        let count = (emitter.count() as usize)
+            .min(PARTICLE_BUDGET.saturating_sub(self.count));
+        crate::RNG.with(|r| {
+            let mut rng = r.borrow_mut();
+            for _ in 0..count {
+                let angle = rotation + rng.gen_range(-emitter.spread()..=emitter.spread());
+                let speed = rng.gen_range(emitter.speed());
+                let life = rng.gen_range(emitter.life());
+                self.particles.push(Particle {
+                    emitter, 
+                    position, 
+                    velocity: base_vel + nalgebra::Vector2::new(
+                        speed * angle.cos(), 
+                        speed * angle.sin(), 
+                    ), 
+                    rotation: rng.gen_range(-std::f32::consts::PI..std::f32::consts::PI), 
+                    size: rng.gen_range(emitter.size()), 
+                    life, 
+                    life_max: life, 
+                });
+            }
+        });
+        self.count += count;
+    }
+
+    pub fn update(&mut self, cycle: &cycle_measure::CycleMeasure) {
+        let mut count = 0;
+        self.particles.retain(|_idx, p| if p.update(cycle) {
+            count += 1;
+            true
+        } else {
+            false
+        });
+        self.count = count;
+    }
+
+    pub fn rendering(&self, renderer: &mut crate::renderer::FSRenderer) {
+        renderer.sprite(crate::render_queue::Layer::Effects).push_instance(&self.particles);
+    }
+}
@@ -0,0 +1,317 @@
+//! 回転を考慮した当たり判定の形状
+
+use tm_wg_wrapper::prelude::nalgebra::{Point2, Vector2};
+
+/// 当たり判定の形状
+/// 回転はワールド座標系での角度
+#[derive(Clone, Copy)]
+pub enum Shape {
+    /// 円
+    Circle {
+        radius: f32, 
+    }, 
+    /// 軸に沿った矩形
+    Aabb {
+        size: Vector2<f32>, 
+    }, 
+    /// 回転した矩形
+    Obb {
+        size: Vector2<f32>, 
+        rotation: f32, 
+    }, 
+    /// カプセル
+    /// 中心を通る長さhalf_len * 2の線分を半径radiusで太らせたもの
+    Capsule {
+        half_len: f32, 
+        radius: f32, 
+        rotation: f32, 
+    }, 
+}
+impl Shape {
+    /// 形状を囲む軸に沿った矩形の大きさ
+    pub fn bounds(&self) -> Vector2<f32> { match self {
+        Shape::Circle { radius } => [radius * 2., radius * 2.].into(), 
+        Shape::Aabb { size } => *size, 
+        Shape::Obb { size, rotation } => {
+            let (s, c) = (rotation.sin().abs(), rotation.cos().abs());
+            [size.x * c + size.y * s, size.x * s + size.y * c].into()
+        }, 
+        Shape::Capsule { half_len, radius, rotation } => [
+            half_len * 2. * rotation.cos().abs() + radius * 2., 
+            half_len * 2. * rotation.sin().abs() + radius * 2., 
+        ].into(), 
+    }}
+
+    /// 形状を囲む円の半径
+    pub fn radius(&self) -> f32 { match self {
+        Shape::Circle { radius } => *radius, 
+        Shape::Aabb { size }
+        | Shape::Obb { size, .. } => (size.x.powi(2) + size.y.powi(2)).sqrt() * 0.5, 
+        Shape::Capsule { half_len, radius, .. } => half_len + radius, 
+    }}
+}
+
+/// 位置の決まった当たり判定
+#[derive(Clone, Copy)]
+pub struct Collider {
+    pub position: Point2<f32>, 
+    pub shape: Shape, 
+}
+impl Collider {
+    /// 形状の組み合わせに応じた判定で交差を調べる
+    pub fn intersects(&self, other: &Collider) -> bool {
+        match (Prim::from(self), Prim::from(other)) {
+            (Prim::Circle(c0, r0), Prim::Circle(c1, r1)) => {
+                let d = c1 - c0;
+                d.x.powi(2) + d.y.powi(2) <= (r0 + r1).powi(2)
+            }, 
+            (Prim::Circle(c, r), Prim::Box(b))
+            | (Prim::Box(b), Prim::Circle(c, r)) => b.point_dist(c) <= r, 
+            (Prim::Circle(c, r), Prim::Segment(a, b, sr))
+            | (Prim::Segment(a, b, sr), Prim::Circle(c, r)) => {
+                point_segment_dist(c, a, b) <= r + sr
+            }, 
+            (Prim::Box(b0), Prim::Box(b1)) => b0.overlaps(&b1), 
+            (Prim::Box(bx), Prim::Segment(a, b, r))
+            | (Prim::Segment(a, b, r), Prim::Box(bx)) => bx.segment_dist(a, b) <= r, 
+            (Prim::Segment(a0, b0, r0), Prim::Segment(a1, b1, r1)) => {
+                segment_segment_dist(a0, b0, a1, b1) <= r0 + r1
+            }, 
+        }
+    }
+}
+
+/// 当たり判定を持つ物体
+pub trait HasShape {
+    fn collider(&self) -> Collider;
+}
+
+/// 二つの物体の当たり判定
+pub fn overlap(a: &impl HasShape, b: &impl HasShape) -> bool {
+    a.collider().intersects(&b.collider())
+}
+
+/// 判定に用いる基本形状
+enum Prim {
+    Circle(Point2<f32>, f32), 
+    Box(OrientedBox), 
+    Segment(Point2<f32>, Point2<f32>, f32), 
+}
+impl From<&Collider> for Prim {
+    fn from(c: &Collider) -> Self { match c.shape {
+        Shape::Circle { radius } => Prim::Circle(c.position, radius), 
+        Shape::Aabb { size } => Prim::Box(OrientedBox::new(c.position, size, 0.)), 
+        Shape::Obb { size, rotation } => Prim::Box(OrientedBox::new(c.position, size, rotation)), 
+        Shape::Capsule { half_len, radius, rotation } => {
+            let d = Vector2::new(rotation.cos(), rotation.sin()) * half_len;
+            Prim::Segment(c.position - d, c.position + d, radius)
+        }, 
+    }}
+}
+
+/// 回転した矩形
+struct OrientedBox {
+    center: Point2<f32>, 
+    half: Vector2<f32>, 
+    axes: [Vector2<f32>; 2], 
+}
+impl OrientedBox {
+    fn new(
+        center: Point2<f32>, 
+        size: Vector2<f32>, 
+        rotation: f32, 
+    ) -> Self { Self {
+        center, 
+        half: size * 0.5, 
+        axes: [
+            Vector2::new(rotation.cos(), rotation.sin()), 
+            Vector2::new(-rotation.sin(), rotation.cos()), 
+        ], 
+    }}
+
+    /// 矩形の局所座標系への変換
+    fn to_local(&self, p: Point2<f32>) -> Point2<f32> {
+        let d = p - self.center;
+        Point2::new(d.dot(&self.axes[0]), d.dot(&self.axes[1]))
+    }
+
+    fn corners(&self) -> [Point2<f32>; 4] {
+        let (x, y) = (self.axes[0] * self.half.x, self.axes[1] * self.half.y);
+        [
+            self.center + x + y, 
+            self.center + x - y, 
+            self.center - x - y, 
+            self.center - x + y, 
+        ]
+    }
+
+    /// 軸へ投影した時の半径
+    fn project_radius(&self, axis: &Vector2<f32>) -> f32 {
+        self.half.x * self.axes[0].dot(axis).abs()
+            + self.half.y * self.axes[1].dot(axis).abs()
+    }
+
+    /// 点までの距離
+    /// 点が内側にあれば0.0
+    fn point_dist(&self, p: Point2<f32>) -> f32 {
+        let l = self.to_local(p);
+        let d = Vector2::new(
+            (l.x.abs() - self.half.x).max(0.), 
+            (l.y.abs() - self.half.y).max(0.), 
+        );
+        (d.x.powi(2) + d.y.powi(2)).sqrt()
+    }
+
+    /// 分離軸判定
+    fn overlaps(&self, other: &OrientedBox) -> bool {
+        let d = other.center - self.center;
+        self.axes.iter()
+            .chain(other.axes.iter())
+            .all(|axis| d.dot(axis).abs()
+                <= self.project_radius(axis) + other.project_radius(axis)
+            )
+    }
+
+    /// 線分までの距離
+    /// 交差していれば0.0
+    fn segment_dist(&self, a: Point2<f32>, b: Point2<f32>) -> f32 {
+        let (la, lb) = (self.to_local(a), self.to_local(b));
+        let min = Point2::new(-self.half.x, -self.half.y);
+        let max = Point2::new(self.half.x, self.half.y);
+        if super::sweep::segment_aabb(la, lb, min, max).is_some() {
+            return 0.
+        }
+        // 交差しなければ、最短距離は端点か角のいずれかで取る
+        self.corners().into_iter()
+            .map(|c| point_segment_dist(c, a, b))
+            .chain([self.point_dist(a), self.point_dist(b)])
+            .fold(f32::MAX, f32::min)
+    }
+}
+
+/// 点と線分の距離
+fn point_segment_dist(p: Point2<f32>, a: Point2<f32>, b: Point2<f32>) -> f32 {
+    let ab = b - a;
+    let len2 = ab.x.powi(2) + ab.y.powi(2);
+    let t = if len2 <= f32::EPSILON {
+        0.
+    } else {
+        ((p - a).dot(&ab) / len2).clamp(0., 1.)
+    };
+    let d = p - (a + ab * t);
+    (d.x.powi(2) + d.y.powi(2)).sqrt()
+}
+
+/// 線分同士の距離
+fn segment_segment_dist(
+    a0: Point2<f32>, 
+    b0: Point2<f32>, 
+    a1: Point2<f32>, 
+    b1: Point2<f32>, 
+) -> f32 {
+    let cross = |o: Point2<f32>, p: Point2<f32>, q: Point2<f32>| {
+        let (u, v) = (p - o, q - o);
+        u.x * v.y - u.y * v.x
+    };
+    let (d0, d1) = (cross(a1, b1, a0), cross(a1, b1, b0));
+    let (d2, d3) = (cross(a0, b0, a1), cross(a0, b0, b1));
+    if d0 * d1 < 0. && d2 * d3 < 0. {
+        return 0.
+    }
+    [
+        point_segment_dist(a0, a1, b1), 
+        point_segment_dist(b0, a1, b1), 
+        point_segment_dist(a1, a0, b0), 
+        point_segment_dist(b1, a0, b0), 
+    ].into_iter().fold(f32::MAX, f32::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(x: f32, y: f32, shape: Shape) -> Collider {
+        Collider { position: Point2::new(x, y), shape }
+    }
+
+    fn circle(x: f32, y: f32, radius: f32) -> Collider {
+        at(x, y, Shape::Circle { radius })
+    }
+
+    #[test]
+    fn rotated_obb_circle_touching() {
+        let obb = at(0., 0., Shape::Obb {
+            size: Vector2::new(40., 20.), 
+            rotation: std::f32::consts::FRAC_PI_4, 
+        });
+        // 回転後の短辺方向の面から約4.14離れた位置
+        assert!(obb.intersects(&circle(0., 20., 5.)));
+        assert!(circle(0., 20., 5.).intersects(&obb));
+        // 長辺方向の端の少し内側
+        let d = 24.5 * std::f32::consts::FRAC_1_SQRT_2;
+        assert!(obb.intersects(&circle(d, d, 5.)));
+    }
+
+    #[test]
+    fn rotated_obb_circle_separated() {
+        let obb = at(0., 0., Shape::Obb {
+            size: Vector2::new(40., 20.), 
+            rotation: std::f32::consts::FRAC_PI_4, 
+        });
+        assert!(!obb.intersects(&circle(0., 20., 3.)));
+        // 回転していない矩形なら接触する位置でも、回転した矩形からは離れている
+        assert!(at(0., 0., Shape::Aabb { size: Vector2::new(40., 20.) }).intersects(&circle(-20., 12., 3.)));
+        assert!(!obb.intersects(&circle(-20., 12., 3.)));
+    }
+
+    #[test]
+    fn capsule_aabb() {
+        let capsule = at(0., 0., Shape::Capsule {
+            half_len: 20., 
+            radius: 5., 
+            rotation: 0., 
+        });
+        // 端の半円が矩形の面に届く
+        assert!(capsule.intersects(&at(29., 0., Shape::Aabb { size: Vector2::new(10., 10.) })));
+        assert!(!capsule.intersects(&at(31., 0., Shape::Aabb { size: Vector2::new(10., 10.) })));
+        // 矩形を貫く
+        assert!(capsule.intersects(&at(0., 0., Shape::Aabb { size: Vector2::new(4., 4.) })));
+        // 縦向きなら同じ位置の矩形に届かない
+        let vertical = at(0., 0., Shape::Capsule {
+            half_len: 20., 
+            radius: 5., 
+            rotation: std::f32::consts::FRAC_PI_2, 
+        });
+        assert!(!vertical.intersects(&at(29., 0., Shape::Aabb { size: Vector2::new(10., 10.) })));
+        assert!(vertical.intersects(&at(0., 29., Shape::Aabb { size: Vector2::new(10., 10.) })));
+    }
+
+    #[test]
+    fn obb_matches_aabb_at_right_angles() {
+        let size = Vector2::new(40., 20.);
+        let cases = [
+            (Shape::Obb { size, rotation: 0. }, Shape::Aabb { size }), 
+            (
+                Shape::Obb { size, rotation: std::f32::consts::FRAC_PI_2 }, 
+                Shape::Aabb { size: Vector2::new(size.y, size.x) }, 
+            ), 
+        ];
+        for (obb, aabb) in cases {
+            let b = obb.bounds() - aabb.bounds();
+            assert!(b.x.abs() < 1e-4 && b.y.abs() < 1e-4);
+            for ix in -8..=8 { for iy in -8..=8 {
+                let (x, y) = (ix as f32 * 4.5, iy as f32 * 4.5);
+                let probes = [
+                    circle(x, y, 3.), 
+                    at(x, y, Shape::Aabb { size: Vector2::new(6., 4.) }), 
+                ];
+                for p in probes {
+                    assert_eq!(
+                        at(0., 0., obb).intersects(&p), 
+                        at(0., 0., aabb).intersects(&p), 
+                    );
+                }
+            }}
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! 移動経路による連続的な当たり判定
+
+use tm_wg_wrapper::prelude::nalgebra::{Point2, Vector2};
+
+/// 線分と矩形の交差判定
+/// 線分上で最初に矩形へ入る位置を、始点0.0から終点1.0の比率で返す
+/// 始点が矩形の内側にある場合は0.0を返す
+pub fn segment_aabb(
+    from: Point2<f32>, 
+    to: Point2<f32>, 
+    min: Point2<f32>, 
+    max: Point2<f32>, 
+) -> Option<f32> {
+    let d = to - from;
+    let mut t_enter = 0f32;
+    let mut t_exit = 1f32;
+    for (o, d, min, max) in [
+        (from.x, d.x, min.x, max.x), 
+        (from.y, d.y, min.y, max.y), 
+    ] {
+        if d.abs() <= f32::EPSILON {
+            // 軸に平行な場合は始点が板の内側にあるかだけを見る
+            if o < min || max < o { return None }
+        } else {
+            let (t0, t1) = ((min - o) / d, (max - o) / d);
+            let (t0, t1) = if t0 <= t1 { (t0, t1) } else { (t1, t0) };
+            t_enter = t_enter.max(t0);
+            t_exit = t_exit.min(t1);
+            if t_exit < t_enter { return None }
+        }
+    }
+    Some(t_enter)
+}
+
+/// 大きさを持った物体の移動経路と矩形の交差判定
+/// 矩形を物体の大きさの半分だけ広げ、中心の線分で判定する
+pub fn swept_aabb(
+    from: Point2<f32>, 
+    to: Point2<f32>, 
+    size: Vector2<f32>, 
+    target_pos: Point2<f32>, 
+    target_size: Vector2<f32>, 
+) -> Option<f32> {
+    let half = (size + target_size) * 0.5;
+    segment_aabb(from, to, target_pos - half, target_pos + half)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn min() -> Point2<f32> { Point2::new(-10., -10.) }
+    fn max() -> Point2<f32> { Point2::new(10., 10.) }
+
+
+    #[test]
+    fn segment_entering() {
+        let t = segment_aabb(Point2::new(-30., 0.), Point2::new(10., 0.), min(), max()).unwrap();
+        assert!((t - 0.5).abs() < 1e-6);
+        // 斜めに角の近くから入る
+        let t = segment_aabb(Point2::new(-20., -30.), Point2::new(20., 10.), min(), max()).unwrap();
+        assert!((t - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn segment_missing() {
+        // 手前で止まる
+        assert!(segment_aabb(Point2::new(-30., 0.), Point2::new(-15., 0.), min(), max()).is_none());
+        // 横を通り過ぎる
+        assert!(segment_aabb(Point2::new(-30., 20.), Point2::new(30., 15.), min(), max()).is_none());
+        // 角をかすめずに通り過ぎる
+        assert!(segment_aabb(Point2::new(-30., 0.), Point2::new(0., 30.), min(), max()).is_none());
+    }
+
+    #[test]
+    fn segment_starting_inside() {
+        assert_eq!(segment_aabb(Point2::new(0., 0.), Point2::new(30., 5.), min(), max()), Some(0.));
+        assert_eq!(segment_aabb(Point2::new(0., 0.), Point2::new(0., 0.), min(), max()), Some(0.));
+    }
+
+    #[test]
+    fn segment_parallel_to_face() {
+        // 面に沿った平行な線分は、板の内側なら入り、外側なら入らない
+        let t = segment_aabb(Point2::new(-30., 5.), Point2::new(30., 5.), min(), max()).unwrap();
+        assert!((t - 1. / 3.).abs() < 1e-6);
+        assert!(segment_aabb(Point2::new(-30., 11.), Point2::new(30., 11.), min(), max()).is_none());
+        assert!(segment_aabb(Point2::new(15., -30.), Point2::new(15., 30.), min(), max()).is_none());
+    }
+
+    #[test]
+    fn swept_aabb_expands_by_size() {
+        let from = Point2::new(-40., 14.);
+        let to = Point2::new(40., 14.);
+        let target_pos = Point2::new(0., 0.);
+        let target_size = Vector2::new(20., 20.);
+        // 点としては外れるが、大きさを持てば当たる
+        assert!(swept_aabb(from, to, Vector2::new(0., 0.), target_pos, target_size).is_none());
+        let t = swept_aabb(from, to, Vector2::new(10., 10.), target_pos, target_size).unwrap();
+        assert!((t - 25. / 80.).abs() < 1e-6);
+    }
+}
@@ -44,7 +44,7 @@ impl GameOver {
         &self, 
         renderer: &mut FSRenderer, 
     ) {
-        renderer.font.draw_type(
+        renderer.text(crate::render_queue::Layer::Overlay).draw_type(
             &font_typing::TypeParam {
                 s: "[!!Game Over!!]",
                 position: [0., 0.],
@@ -7,12 +7,30 @@
 use tm_wg_wrapper::{
     util::simple2d::{
         font_typing, 
+        InstanceGen, 
+        img_obj::ImgObjInstance, 
+        instance::buffer::InstanceArray, 
     }, 
     prelude::Window
 };
 
 use crate::renderer::FSRenderer;
 
+/// ポーズ中であることを示す見出しの画像
+struct Banner;
+impl InstanceGen<ImgObjInstance> for Banner {
+    fn generate(&self, instances: &mut InstanceArray<ImgObjInstance>) {
+        instances.push(ImgObjInstance {
+            position: [0., 96.], 
+            size: [256., 64.], 
+            rotation: 0., 
+            tex_coord: crate::atlas::locate("indicator", [0., 0.]), 
+            tex_size: [128., 32.], 
+            tex_rev: [false, false], 
+        })
+    }
+}
+
 pub struct GamePause {
     pub do_exit: bool, 
 }
@@ -44,10 +62,10 @@ impl GamePause {
         &self, 
         renderer: &mut FSRenderer, 
     ) {
-        renderer.font.draw_type(
+        renderer.sprite(crate::render_queue::Layer::Overlay).push_instance(&Banner);
+        renderer.text(crate::render_queue::Layer::Overlay).draw_type(
             &font_typing::TypeParam {
                 s: "\
-                [Pause]\n\n\
                 Escape pause mode: `Escape` or\n\
                 `P` key press moment\n\
                 Exit: `Escape` Key press over 1sec\
@@ -13,6 +13,10 @@ use tm_wg_wrapper::prelude::*;
 
 pub mod log;
 pub mod renderer;
+pub mod render_queue;
+pub mod assets;
+pub mod atlas;
+pub mod viewport;
 pub mod game;
 pub mod game_pause;
 pub mod game_over;
@@ -30,7 +34,7 @@ impl scene_frame::FrameParam for FSFrameParam {
     ) -> Result<(), Box<dyn std::error::Error>> {
         self.cycle_measure.update();
         self.visible_area = Some(simple2d::types::VisibleField::new(
-            &renderer.camera.camera
+            &renderer.play_camera()
         ));
         Ok(())
     }
@@ -52,8 +56,12 @@ impl scene_frame::Scene for FSFrame {
     fn window_builder() -> winit::window::WindowBuilder {
         winit::window::WindowBuilder::new()
             .with_active(true)
-            .with_resizable(false)
-            .with_inner_size(winit::dpi::PhysicalSize::new(640, 960))
+            .with_resizable(true)
+            .with_inner_size(winit::dpi::PhysicalSize::new(
+                viewport::LOGICAL_SIZE[0] as u32, 
+                viewport::LOGICAL_SIZE[1] as u32, 
+            ))
+            .with_min_inner_size(winit::dpi::PhysicalSize::new(160, 240))
             .with_title("Ferris shooting")
     }
 
@@ -112,7 +120,10 @@ impl scene_frame::Scene for FSFrame {
         &mut self, 
         delta: (f64, f64), 
     ) { match self {
-        FSFrame::Game(g) => g.input_mouse_motion([delta.0 as f32, -delta.1 as f32]),
+        // ウィンドウ上の移動量を論理的な画面の単位へ直す
+        FSFrame::Game(g) => g.input_mouse_motion(
+            crate::viewport::current().to_logical([delta.0 as f32, -delta.1 as f32])
+        ),
         FSFrame::GamePause(_) => {},
         FSFrame::GameOver(_) => {}, 
     }}
@@ -127,6 +138,7 @@ impl scene_frame::Scene for FSFrame {
         &mut self, 
         size: winit::dpi::PhysicalSize<u32>, 
     ) {
+        // 描画側がサーフェスの大きさに追従して拡大率を合わせるため、ゲームプレイへの影響は無い
     }
 
     fn process(
@@ -144,7 +156,7 @@ impl scene_frame::Scene for FSFrame {
     > { match self {
         FSFrame::Game(g) => {
             frame_param.visible_area = Some(frame_param.visible_area.take().unwrap_or(
-                simple2d::types::VisibleField::new(&renderer.camera.camera)
+                simple2d::types::VisibleField::new(&renderer.play_camera())
             ));
             g.update(
                 is_top, 
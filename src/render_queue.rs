@@ -0,0 +1,199 @@
+//! 描画の重なり順を明示するための描画キュー
+//! 描画物は層を指定して積み、描画時に層の順に並べて1枚のアトラスでまとめて描画する
+//! 文字もアトラスに詰め込んだフォントから並べるため、層の描画に必要なテクスチャは1枚で済む
+
+use tm_wg_wrapper::{
+    prelude::*, 
+    util::simple2d::{
+        InstanceGen, 
+        Simple2DRender, 
+        instance::buffer::InstanceArray, 
+    }, 
+};
+use simple2d::{
+    S2DCamera, 
+    SquareShared, 
+    ImagedShared, 
+    img_obj::{self, ImgObjInstance}, 
+    font_typing, 
+};
+
+/// 描画の層
+/// 宣言順に奥から手前へ描画する
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Layer {
+    /// 背景
+    Background, 
+    /// 敵・敵に重ねる表示・アイテム
+    Enemies, 
+    /// 自機の発射したギア
+    PlayerGears, 
+    /// 敵の弾
+    EnemyProjectiles, 
+    /// 自機
+    Player, 
+    /// 見た目だけの演出
+    Effects, 
+    /// 画面の情報表示
+    Hud, 
+    /// ポーズ画面などの最前面の表示
+    Overlay, 
+}
+impl Layer {
+    pub const ALL: [Layer; 8] = [
+        Layer::Background, 
+        Layer::Enemies, 
+        Layer::PlayerGears, 
+        Layer::EnemyProjectiles, 
+        Layer::Player, 
+        Layer::Effects, 
+        Layer::Hud, 
+        Layer::Overlay, 
+    ];
+
+    pub fn index(&self) -> usize { *self as usize }
+}
+
+/// 1つの層に積まれた描画物
+pub struct LayerBatch {
+    instances: InstanceArray<ImgObjInstance>, 
+}
+impl LayerBatch {
+    /// 画像を積む
+    pub fn push_instance(&mut self, gen: &impl InstanceGen<ImgObjInstance>) {
+        gen.generate(&mut self.instances)
+    }
+}
+
+/// 文字列を1つの層へ積むための参照
+pub struct TextBatch<'a> {
+    batch: &'a mut LayerBatch, 
+    font: &'a font_typing::FontSet, 
+}
+impl TextBatch<'_> {
+    /// 文字列を積む
+    /// 縦書きは用いないため、方向の指定によらず横書きで並べる
+    pub fn draw_type(&mut self, param: &font_typing::TypeParam) {
+        let model = |c: char| self.font.fonts.get(&c).unwrap_or(&self.font.default);
+        let cell = self.font.default.tex_size;
+        let advance = cell[0] * param.size_ratio[0];
+        let line_height = cell[1] * param.size_ratio[1];
+
+        let lines = param.s.split('\n').collect::<Vec<_>>();
+        let height = lines.len() as f32 * line_height;
+        let top = match param.align_v {
+            font_typing::TypeAlignV::Top => 0., 
+            font_typing::TypeAlignV::Middle => height * 0.5, 
+            font_typing::TypeAlignV::Bottom => height, 
+        };
+        let (sin, cos) = param.rotation.sin_cos();
+        for (l, line) in lines.iter().enumerate() {
+            let width = line.chars().count() as f32 * advance;
+            let left = match param.align_h {
+                font_typing::TypeAlignH::Left => 0., 
+                font_typing::TypeAlignH::Center => -width * 0.5, 
+                font_typing::TypeAlignH::Right => -width, 
+            };
+            for (i, c) in line.chars().enumerate() {
+                let m = model(c);
+                // 基準位置からの相対位置を回転させる
+                let x = left + (i as f32 + 0.5) * advance + m.base_line[0] * param.size_ratio[0];
+                let y = top - (l as f32 + 0.5) * line_height + m.base_line[1] * param.size_ratio[1];
+                self.batch.instances.push(ImgObjInstance {
+                    position: [
+                        param.position[0] + x * cos - y * sin, 
+                        param.position[1] + x * sin + y * cos, 
+                    ], 
+                    size: [m.tex_size[0] * param.size_ratio[0], m.tex_size[1] * param.size_ratio[1]], 
+                    rotation: param.rotation, 
+                    tex_coord: crate::atlas::locate("font", m.tex_coord), 
+                    tex_size: m.tex_size, 
+                    tex_rev: [false, false], 
+                });
+            }
+        }
+    }
+}
+
+/// 積まれた層を奥から順に並べたもの
+struct Ordered<'a>(&'a [LayerBatch]);
+impl InstanceGen<ImgObjInstance> for Ordered<'_> {
+    fn generate(&self, instances: &mut InstanceArray<ImgObjInstance>) {
+        for batch in self.0.iter() {
+            for i in batch.instances.iter() {
+                instances.push(*i)
+            }
+        }
+    }
+}
+
+/// 層ごとの描画物のキュー
+pub struct RenderQueue {
+    /// 層の描画に用いる、アトラスを読み込んだ描画
+    atlas: img_obj::ImgObjRender, 
+    /// 層を経由しない描画に用いる、同じアトラスを読み込んだ描画
+    /// 1つのエンコーダ内で同じインスタンスバッファへ2度書き込まないよう分けている
+    direct: img_obj::ImgObjRender, 
+    batches: Vec<LayerBatch>, 
+    font: font_typing::FontSet, 
+}
+impl RenderQueue {
+    pub fn new(
+        gfx: &GfxCtx, 
+        imaged: &ImagedShared, 
+        atlas_path: &str, 
+        font: font_typing::FontSet, 
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            atlas: img_obj::ImgObjRender::new(gfx, imaged, atlas_path)?, 
+            direct: img_obj::ImgObjRender::new(gfx, imaged, atlas_path)?, 
+            batches: Layer::ALL.iter()
+                .map(|_| LayerBatch { instances: InstanceArray::new() })
+                .collect(), 
+            font, 
+        })
+    }
+
+    /// 画像を積む層
+    pub fn sprite(&mut self, layer: Layer) -> &mut LayerBatch {
+        &mut self.batches[layer.index()]
+    }
+
+    /// 文字列を積む層
+    pub fn text(&mut self, layer: Layer) -> TextBatch<'_> {
+        TextBatch {
+            batch: &mut self.batches[layer.index()], 
+            font: &self.font, 
+        }
+    }
+
+    /// 積まれた描画物を層の順に1回で描画し、キューを空にする
+    pub fn rendering(
+        &mut self, 
+        gfx: &GfxCtx, 
+        encoder: &mut wgpu::CommandEncoder, 
+        view: &wgpu::TextureView, 
+        camera: &S2DCamera, 
+        shared: (&SquareShared, &ImagedShared, &img_obj::ImgObjRenderShared), 
+    ) {
+        self.atlas.push_instance(&Ordered(&self.batches));
+        self.batches.iter_mut().for_each(|b| b.instances.clear());
+        self.atlas.rendering(gfx, encoder, view, camera, shared);
+    }
+
+    /// 層を経由せず、指定したカメラで直接描画する
+    /// カメラ演出の影響を受けない表示に用いる
+    /// 層の描画とは別のバッファを用いるが、1フレームに呼べるのは1回まで
+    pub fn rendering_direct(
+        &mut self, 
+        gen: &impl InstanceGen<ImgObjInstance>, 
+        gfx: &GfxCtx, 
+        encoder: &mut wgpu::CommandEncoder, 
+        view: &wgpu::TextureView, 
+        camera: &S2DCamera, 
+        shared: (&SquareShared, &ImagedShared, &img_obj::ImgObjRenderShared), 
+    ) {
+        self.direct.push_instance(gen);
+        self.direct.rendering(gfx, encoder, view, camera, shared);
+    }
+}
@@ -1,9 +1,7 @@
 use std::io::Read;
 
-use tm_wg_wrapper::{
-    prelude::*, 
-    util::simple2d::Simple2DRender, 
-};
+use tm_wg_wrapper::prelude::*;
+use crate::render_queue::{self, Layer};
 use simple2d::{
     S2DCamera, 
     SquareShared, 
@@ -14,34 +12,30 @@ use simple2d::{
 
 pub struct FSRenderer {
     pub camera: S2DCamera, 
+    /// 帯の描画に用いる、演出の影響を受けないカメラ
+    frame_camera: S2DCamera, 
     square: SquareShared, 
     imaged: ImagedShared, 
 
     img_obj: img_obj::ImgObjRenderShared, 
 
-    pub ferris: img_obj::ImgObjRender, 
-    pub aim: img_obj::ImgObjRender, 
-    pub gear: img_obj::ImgObjRender, 
-    pub enemy: img_obj::ImgObjRender, 
-    pub font: font_typing::FontTypeRender, 
-    pub indicator: img_obj::ImgObjRender, 
+    queue: render_queue::RenderQueue, 
 }
 impl FSRenderer {
     pub fn new(
         gfx: &GfxCtx
     ) -> Result<Self, Box<dyn std::error::Error>> {
-        let camera = S2DCamera::new(
-            simple2d::types::Camera {
-                position: [0., 0.].into(),
-                size: [
-                    gfx.config.width as f32, 
-                    gfx.config.height as f32, 
-                ].into(),
-                zoom: 1.,
-                rotation: 0.,
-            }, 
-            gfx, 
-        );
+        let window_camera = || simple2d::types::Camera {
+            position: [0., 0.].into(),
+            size: [
+                gfx.config.width as f32, 
+                gfx.config.height as f32, 
+            ].into(),
+            zoom: 1.,
+            rotation: 0.,
+        };
+        let camera = S2DCamera::new(window_camera(), gfx);
+        let frame_camera = S2DCamera::new(window_camera(), gfx);
         let square = SquareShared::new(gfx);
         let imaged = ImagedShared::new(gfx);
 
@@ -51,124 +45,63 @@ impl FSRenderer {
             &imaged, 
         );
 
-        let ferris = img_obj::ImgObjRender::new(
-            gfx, 
-            &imaged, 
-            "./assets/images/ferris.png"
-        )?;
-        let aim = img_obj::ImgObjRender::new(
-            gfx, 
-            &imaged, 
-            "./assets/images/aim.png"
-        )?;
-        let gear = img_obj::ImgObjRender::new(
-            gfx, 
-            &imaged, 
-            "./assets/images/gear.png"
-        )?;
-        let enemy = img_obj::ImgObjRender::new(
-            gfx, 
-            &imaged, 
-            "./assets/images/enemy_sprite.png"
-        )?;
-        let font = img_obj::ImgObjRender::new(
-            gfx, 
-            &imaged, 
-            "./assets/images/font.png", 
-        )?;
-        let font_set = font_typing::FontSet {
-            fonts: [
-                ' ', '!', '"', '#', 
-                '$', '%', '&', '\'', 
-                '(', ')', '*', '+', 
-                ',', '-', '.', '/', 
-                '0', '1', '2', '3', 
-                '4', '5', '6', '7', 
-                '8', '9', ':', ';', 
-                '<', '=', '>', '?', 
-                '@', 'A', 'B', 'C', 
-                'D', 'E', 'F', 'G', 
-                'H', 'I', 'J', 'K', 
-                'L', 'M', 'N', 'O', 
-                'P', 'Q', 'R', 'S', 
-                'T', 'U', 'V', 'W', 
-                'X', 'Y', 'Z', '[', 
-                '\\', ']', '^', '_', 
-                '`', 'a', 'b', 'c', 
-                'd', 'e', 'f', 'g', 
-                'h', 'i', 'j', 'k', 
-                'l', 'm', 'n', 'o', 
-                'p', 'q', 'r', 's', 
-                't', 'u', 'v', 'w', 
-                'x', 'y', 'z', '{', 
-                '|', '}', '~', '\0', 
-                'ｱ', 'ｲ', 'ｳ', 'ｴ', 
-                'ｵ', 'ｶ', 'ｷ', 'ｸ', 
-                'ｹ', 'ｺ', 'ｻ', 'ｼ', 
-                'ｽ', 'ｾ', 'ｿ', 'ﾀ', 
-                'ﾁ', 'ﾂ', 'ﾃ', 'ﾄ', 
-                'ﾅ', 'ﾆ', 'ﾇ', 'ﾈ', 
-                'ﾉ', 'ﾊ', 'ﾋ', 'ﾌ', 
-                'ﾍ', 'ﾎ', 'ﾏ', 'ﾐ', 
-                'ﾑ', 'ﾒ', 'ﾓ', 'ﾔ', 
-                'ヰ', 'ﾕ', 'ヱ', 'ﾖ', 
-                'ﾗ', 'ﾘ', 'ﾙ', 'ﾚ', 
-                'ﾛ', 'ﾜ', 'ｦ', 'ﾝ', 
-                'ｧ', 'ｨ', 'ｩ', 'ｪ', 
-                'ｫ', 'ｬ', 'ｭ', 'ｮ', 
-                'ﾞ', 'ﾟ', '､', '｡', 
-                '･', '\0', '\0', '\0', 
-            ].into_iter()
-                .enumerate()
-                .map(|(i, c)| (
-                    c, [16. * (i % 16) as f32, 32. * (i / 16) as f32]
-                ))
-                .filter_map(|(c, s)| if c != '\0'{ Some((
-                    c, 
-                    font_typing::CharModel {
-                        tex_coord: s,
-                        tex_size: [16., 32.],
-                        base_line: [0., 0.],
-                    }
-                ))} else {
-                    None
-                })
-                .collect(),
-            default: font_typing::CharModel { 
-                tex_coord: [16. * 15., 32. * 5.], 
-                tex_size: [16., 32.], 
-                base_line: [0., 0.] 
-            },
-        };
-        let font = font_typing::FontTypeRender::new(
-            font, 
-            font_set, 
-        );
-        let indicator = img_obj::ImgObjRender::new(
+        let (atlas, atlas_image) = crate::atlas::Atlas::build(crate::assets::SPRITES)?;
+        crate::atlas::install(atlas);
+        // 読み込みが終われば一時ファイルは削除される
+        let staged = crate::atlas::StagedImage::write(&atlas_image)?;
+        let queue = render_queue::RenderQueue::new(
             gfx, 
             &imaged, 
-            "./assets/images/indicator.png", 
+            staged.path_str()?, 
+            font_set(), 
         )?;
+        drop(staged);
 
-        Ok(Self {
+        let mut renderer = Self {
             camera,
+            frame_camera, 
             square,
             imaged,
             img_obj,
-            ferris,
-            aim, 
-            gear,
-            enemy, 
-            font, 
-            indicator, 
-        })
+            queue, 
+        };
+        renderer.resize(winit::dpi::PhysicalSize::new(
+            gfx.config.width, 
+            gfx.config.height, 
+        ));
+        Ok(renderer)
+    }
+
+    /// カメラ演出の影響を含まない、ゲームプレイ用のカメラ
+    /// ウィンドウの大きさに依らず、論理的な画面の大きさとする
+    pub fn play_camera(&self) -> simple2d::types::Camera {
+        simple2d::types::Camera {
+            position: [0., 0.].into(), 
+            size: crate::viewport::LOGICAL_SIZE.into(), 
+            zoom: 1., 
+            rotation: 0., 
+        }
+    }
+
+    /// 指定した層へ画像を積む
+    pub fn sprite(&mut self, layer: Layer) -> &mut render_queue::LayerBatch {
+        self.queue.sprite(layer)
+    }
+
+    /// 指定した層へ文字列を積む
+    pub fn text(&mut self, layer: Layer) -> render_queue::TextBatch<'_> {
+        self.queue.text(layer)
     }
 
+    /// ウィンドウの大きさの変更
+    /// 論理的な画面がウィンドウに収まるよう拡大率を合わせる
     pub fn resize(&mut self, size: winit::dpi::PhysicalSize<u32>) {
-        self.camera.camera.size = [
-            size.width as f32, 
-            size.height as f32, 
-        ].into();
+        let viewport = crate::viewport::Viewport::new([size.width, size.height]);
+        crate::viewport::install(viewport);
+        for camera in [&mut self.camera.camera, &mut self.frame_camera.camera] {
+            camera.size = viewport.window.into();
+            camera.zoom = viewport.scale();
+        }
     }
 }
 impl Renderer for FSRenderer {
@@ -178,6 +111,17 @@ impl Renderer for FSRenderer {
         view: &wgpu::TextureView, 
         gfx: &GfxCtx, 
     ) {
+        // サーフェスの大きさに追従する
+        if crate::viewport::current().window != [
+            gfx.config.width as f32, 
+            gfx.config.height as f32, 
+        ] {
+            self.resize(winit::dpi::PhysicalSize::new(
+                gfx.config.width, 
+                gfx.config.height, 
+            ))
+        }
+
         let mut encoder = gfx.device.create_command_encoder(
             &wgpu::CommandEncoderDescriptor { label: Some("fs renderer encoder") }
         );
@@ -188,9 +132,9 @@ impl Renderer for FSRenderer {
                 resolve_target: None,
                 ops: wgpu::Operations {
                     load: wgpu::LoadOp::Clear(wgpu::Color {
-                        r: 0.25,
-                        g: 0.25,
-                        b: 0.25,
+                        r: 0.03,
+                        g: 0.04,
+                        b: 0.07,
                         a: 1.,
                     }),
                     store: true,
@@ -198,36 +142,88 @@ impl Renderer for FSRenderer {
             })],
             depth_stencil_attachment: None,
         });
-        self.gear.rendering(gfx, &mut encoder, view, &self.camera, (
-            &self.square, 
-            &self.imaged, 
-            &self.img_obj, 
-        ));
-        self.enemy.rendering(gfx, &mut encoder, view, &self.camera, (
-            &self.square, 
-            &self.imaged, 
-            &self.img_obj, 
-        ));
-        self.ferris.rendering(gfx, &mut encoder, view, &self.camera, (
-            &self.square, 
-            &self.imaged, 
-            &self.img_obj, 
-        ));
-        self.aim.rendering(gfx, &mut encoder, view, &self.camera, (
-            &self.square, 
-            &self.imaged, 
-            &self.img_obj, 
-        ));
-        self.font.rendering(gfx, &mut encoder, view, &self.camera, (
-            &self.square, 
-            &self.imaged, 
-            &self.img_obj, 
-        ));
-        self.indicator.rendering(gfx, &mut encoder, view, &self.camera, (
+        self.queue.rendering(gfx, &mut encoder, view, &self.camera, (
             &self.square, 
             &self.imaged, 
             &self.img_obj, 
         ));
+        // 帯は演出の影響を受けないカメラで、層とは別の描画から同じアトラスを用いて描画する
+        self.queue.rendering_direct(
+            &crate::viewport::Letterbox { viewport: crate::viewport::current() }, 
+            gfx, 
+            &mut encoder, 
+            view, 
+            &self.frame_camera, 
+            (&self.square, &self.imaged, &self.img_obj), 
+        );
         gfx.queue.submit(Some(encoder.finish()));
     }
-}
\ No newline at end of file
+}
+
+/// 文字の配置
+fn font_set() -> font_typing::FontSet {
+    font_typing::FontSet {
+        fonts: [
+            ' ', '!', '"', '#', 
+            '$', '%', '&', '\'', 
+            '(', ')', '*', '+', 
+            ',', '-', '.', '/', 
+            '0', '1', '2', '3', 
+            '4', '5', '6', '7', 
+            '8', '9', ':', ';', 
+            '<', '=', '>', '?', 
+            '@', 'A', 'B', 'C', 
+            'D', 'E', 'F', 'G', 
+            'H', 'I', 'J', 'K', 
+            'L', 'M', 'N', 'O', 
+            'P', 'Q', 'R', 'S', 
+            'T', 'U', 'V', 'W', 
+            'X', 'Y', 'Z', '[', 
+            '\\', ']', '^', '_', 
+            '`', 'a', 'b', 'c', 
+            'd', 'e', 'f', 'g', 
+            'h', 'i', 'j', 'k', 
+            'l', 'm', 'n', 'o', 
+            'p', 'q', 'r', 's', 
+            't', 'u', 'v', 'w', 
+            'x', 'y', 'z', '{', 
+            '|', '}', '~', '\0', 
+            'ｱ', 'ｲ', 'ｳ', 'ｴ', 
+            'ｵ', 'ｶ', 'ｷ', 'ｸ', 
+            'ｹ', 'ｺ', 'ｻ', 'ｼ', 
+            'ｽ', 'ｾ', 'ｿ', 'ﾀ', 
+            'ﾁ', 'ﾂ', 'ﾃ', 'ﾄ', 
+            'ﾅ', 'ﾆ', 'ﾇ', 'ﾈ', 
+            'ﾉ', 'ﾊ', 'ﾋ', 'ﾌ', 
+            'ﾍ', 'ﾎ', 'ﾏ', 'ﾐ', 
+            'ﾑ', 'ﾒ', 'ﾓ', 'ﾔ', 
+            'ヰ', 'ﾕ', 'ヱ', 'ﾖ', 
+            'ﾗ', 'ﾘ', 'ﾙ', 'ﾚ', 
+            'ﾛ', 'ﾜ', 'ｦ', 'ﾝ', 
+            'ｧ', 'ｨ', 'ｩ', 'ｪ', 
+            'ｫ', 'ｬ', 'ｭ', 'ｮ', 
+            'ﾞ', 'ﾟ', '､', '｡', 
+            '･', '\0', '\0', '\0', 
+        ].into_iter()
+            .enumerate()
+            .map(|(i, c)| (
+                c, [16. * (i % 16) as f32, 32. * (i / 16) as f32]
+            ))
+            .filter_map(|(c, s)| if c != '\0'{ Some((
+                c, 
+                font_typing::CharModel {
+                    tex_coord: s,
+                    tex_size: [16., 32.],
+                    base_line: [0., 0.],
+                }
+            ))} else {
+                None
+            })
+            .collect(),
+        default: font_typing::CharModel { 
+            tex_coord: [16. * 15., 32. * 5.], 
+            tex_size: [16., 32.], 
+            base_line: [0., 0.] 
+        },
+    }
+}
@@ -0,0 +1,98 @@
+//! ウィンドウの大きさに依らない論理的な画面の実装
+//! ゲームプレイは常に論理的な大きさの画面で行い、ウィンドウへは縦横比を保って拡大し、余白は帯で覆う
+
+use std::cell::Cell;
+
+use tm_wg_wrapper::{
+    prelude::*, 
+    util::simple2d::{
+        InstanceGen, 
+        img_obj::ImgObjInstance, 
+    }, 
+};
+
+/// 論理的な画面の大きさ
+pub const LOGICAL_SIZE: [f32; 2] = [640., 960.];
+
+thread_local! {
+    static VIEWPORT: Cell<Viewport> = Cell::new(Viewport::new([
+        LOGICAL_SIZE[0] as u32, 
+        LOGICAL_SIZE[1] as u32, 
+    ]));
+}
+
+/// ウィンドウと論理的な画面の対応
+#[derive(Clone, Copy)]
+pub struct Viewport {
+    /// ウィンドウの大きさ
+    pub window: [f32; 2], 
+}
+impl Viewport {
+    pub fn new(window: [u32; 2]) -> Self { Self {
+        window: [window[0].max(1) as f32, window[1].max(1) as f32], 
+    }}
+
+    /// 論理的な画面をウィンドウへ収める倍率
+    pub fn scale(&self) -> f32 {
+        (self.window[0] / LOGICAL_SIZE[0]).min(self.window[1] / LOGICAL_SIZE[1])
+    }
+
+    /// ウィンドウ上の移動量を論理的な画面上の移動量へ変換する
+    pub fn to_logical(&self, delta: [f32; 2]) -> [f32; 2] {
+        let scale = self.scale();
+        [delta[0] / scale, delta[1] / scale]
+    }
+
+    /// ウィンドウ全体を論理的な画面の単位で表した時の半分の大きさ
+    pub fn window_half(&self) -> [f32; 2] {
+        let scale = self.scale();
+        [self.window[0] / scale * 0.5, self.window[1] / scale * 0.5]
+    }
+}
+
+/// 描画に用いる対応を設定する
+pub fn install(viewport: Viewport) {
+    VIEWPORT.with(|v| v.set(viewport))
+}
+
+/// 設定済みの対応
+pub fn current() -> Viewport {
+    VIEWPORT.with(|v| v.get())
+}
+
+/// 論理的な画面の外側を覆う帯
+/// 演出の影響を受けないカメラで描画する
+pub struct Letterbox {
+    pub viewport: Viewport, 
+}
+impl InstanceGen<ImgObjInstance> for Letterbox {
+    fn generate(
+        &self, 
+        instances: &mut simple2d::instance::buffer::InstanceArray<ImgObjInstance>
+    ) {
+        let outer = self.viewport.window_half();
+        let inner = [LOGICAL_SIZE[0] * 0.5, LOGICAL_SIZE[1] * 0.5];
+        let tex_coord = crate::atlas::locate("letterbox", [2., 2.]);
+        let mut bar = |position: [f32; 2], size: [f32; 2]| {
+            if size[0] <= 0. || size[1] <= 0. { return }
+            instances.push(ImgObjInstance {
+                position, 
+                size, 
+                rotation: 0., 
+                tex_coord, 
+                tex_size: [4., 4.], 
+                tex_rev: [false, false], 
+            })
+        };
+        // 左右
+        let width = outer[0] - inner[0];
+        let x = (outer[0] + inner[0]) * 0.5;
+        bar([-x, 0.], [width, outer[1] * 2.]);
+        bar([x, 0.], [width, outer[1] * 2.]);
+        // 上下
+        let height = outer[1] - inner[1];
+        let y = (outer[1] + inner[1]) * 0.5;
+        bar([0., y], [outer[0] * 2., height]);
+        bar([0., -y], [outer[0] * 2., height]);
+    }
+}